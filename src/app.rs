@@ -2,8 +2,9 @@ use crate::eventbrite_attendees::Attendee;
 use crate::eventbrite_auth::{load_config, save_config};
 use arboard::Clipboard;
 use crossterm::event::KeyCode;
-use log::{debug, error};
+use log::{debug, error, warn};
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum AppView {
     MainMenu,
     ListNextEventAttendeesMenu,
@@ -11,6 +12,11 @@ pub enum AppView {
     SetClientIdPopup,
     SetClientSecretPopup,
     FindByNameMenu,
+    AccountsMenu,
+    InspectorMenu,
+    CommandMode,
+    ExportPopup,
+    ThemeMenu,
 }
 
 pub struct NameSearchState {
@@ -40,13 +46,97 @@ pub struct App {
     pub attendees: Vec<Attendee>,
     pub input_buffer: String,
     pub event_date: Option<String>,
+    pub event_id: Option<String>,
     pub selected_row: usize,
     pub selected_col: usize,
     pub name_search_state: NameSearchState,
+    pub accounts_menu_index: usize,
+    pub attendee_filter: String,
+    pub filtering: bool,
+    pub inspector_menu_index: usize,
+    pub status_message: Option<String>,
+    /// `fetched_at` of `attendees` when they came from the offline cache
+    /// rather than a live API call; `None` means the list is fresh.
+    pub attendees_cached_at: Option<u64>,
+    /// Buffer for the `:`-prefixed command palette (`AppView::CommandMode`).
+    pub command_input: String,
+    view_before_command: AppView,
+    /// `(pages_loaded, total_pages)` while `load_attendees` is paging in the
+    /// background; `total_pages` is `0` when Eventbrite hasn't reported a
+    /// page count yet. `None` once loading is finished or not in progress.
+    pub loading_progress: Option<(usize, usize)>,
+    loading_rx: Option<std::sync::mpsc::Receiver<AttendeesLoadEvent>>,
+    /// `(account, synced, pending)` from an in-flight `reconcile_checkins`
+    /// background thread; `None` when no reconciliation is running. Prevents the
+    /// blocking HTTP calls in `checkin::reconcile` from ever running on the
+    /// UI thread, where they'd freeze keystrokes and redraws.
+    reconcile_rx: Option<std::sync::mpsc::Receiver<(crate::eventbrite_auth::Account, usize, usize)>>,
+    /// Rows of `filtered_attendees()` marked for a bulk action (currently
+    /// the `'y'` clipboard export), toggled with `Space` and reset whenever
+    /// the filter changes.
+    pub selected: std::collections::BTreeSet<usize>,
+    view_before_export: AppView,
+    /// Named style slots resolved from `Config::theme_name`, consulted by
+    /// the render layer instead of literal `Color` values (see `Theme` for
+    /// the accents that keep fixed colors).
+    pub theme: crate::theme::Theme,
+    pub theme_menu_index: usize,
+    /// The on-disk config, decrypted once (here, or on explicit mutation)
+    /// rather than on every render: `load_config` can block on a passphrase
+    /// prompt, which must never happen from inside a `draw_*` function.
+    pub config: crate::eventbrite_auth::Config,
+}
+
+enum AttendeesLoadEvent {
+    Progress(usize, usize),
+    Done(
+        crate::eventbrite_auth::Account,
+        Result<(Vec<Attendee>, String, String, Option<u64>), crate::eventbrite_attendees::EventbriteError>,
+    ),
+}
+
+/// Fetches attendees for `account`, transparently refreshing its access
+/// token and retrying once if the cached one was rejected (401) instead of
+/// surfacing the error straight to the user. Takes (and returns) an owned
+/// `Account` clone rather than reaching for the active account itself,
+/// since this runs on a background thread (see `App::load_attendees`) and
+/// must never touch the on-disk config — `get_access_token` only mutates
+/// the `Account` in memory, so the caller is responsible for merging any
+/// refreshed token back into `self.config` once this returns.
+fn fetch_attendees_with_token_retry(
+    mut account: crate::eventbrite_auth::Account,
+    mut on_progress: impl FnMut(usize, usize),
+) -> (
+    crate::eventbrite_auth::Account,
+    Result<(Vec<Attendee>, String, String, Option<u64>), crate::eventbrite_attendees::EventbriteError>,
+) {
+    use crate::eventbrite_attendees::EventbriteError;
+
+    let token = match crate::eventbrite_auth::get_access_token(&mut account, false) {
+        Ok(token) => token,
+        Err(err) => {
+            return (account, Err(EventbriteError::Http { status: 0, body: err.to_string() }));
+        }
+    };
+
+    let result = match crate::eventbrite_attendees::get_attendees_from_api(&token, &mut on_progress) {
+        Err(EventbriteError::Auth) => {
+            warn!("Access token rejected while loading attendees, attempting a silent refresh");
+            match crate::eventbrite_auth::get_access_token(&mut account, true) {
+                Ok(token) => crate::eventbrite_attendees::get_attendees_from_api(&token, on_progress),
+                Err(err) => Err(EventbriteError::Http { status: 401, body: err.to_string() }),
+            }
+        }
+        other => other,
+    };
+
+    (account, result)
 }
 
 impl App {
     pub fn new() -> Self {
+        let config = load_config().unwrap_or_default();
+        let theme_name = config.theme_name;
         Self {
             view: AppView::MainMenu,
             main_menu_index: 0,
@@ -54,41 +144,243 @@ impl App {
             attendees: vec![],
             input_buffer: String::new(),
             event_date: Some(String::new()),
+            event_id: None,
             selected_row: 0,
             selected_col: 0,
             name_search_state: NameSearchState::default(),
+            accounts_menu_index: 0,
+            attendee_filter: String::new(),
+            filtering: false,
+            inspector_menu_index: 0,
+            status_message: None,
+            attendees_cached_at: None,
+            command_input: String::new(),
+            view_before_command: AppView::MainMenu,
+            loading_progress: None,
+            loading_rx: None,
+            reconcile_rx: None,
+            selected: std::collections::BTreeSet::new(),
+            view_before_export: AppView::MainMenu,
+            theme: theme_name.resolve(),
+            theme_menu_index: 0,
+            config,
+        }
+    }
+
+    /// Attendees currently visible in `ListNextEventAttendeesMenu`, narrowed
+    /// by `attendee_filter` (fuzzy, accent-insensitive) and ranked best match
+    /// first. Returns every attendee, in original order, when no filter is
+    /// active.
+    pub fn filtered_attendees(&self) -> Vec<&Attendee> {
+        if self.attendee_filter.trim().is_empty() {
+            return self.attendees.iter().collect();
+        }
+
+        let mut scored: Vec<(i32, &Attendee)> = self
+            .attendees
+            .iter()
+            .filter_map(|attendee| {
+                let full_name = format!(
+                    "{} {}",
+                    attendee.profile.first_name.as_deref().unwrap_or(""),
+                    attendee.profile.last_name.as_deref().unwrap_or("")
+                );
+                crate::fuzzy::fuzzy_score(&self.attendee_filter, &full_name)
+                    .map(|score| (score, attendee))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, attendee)| attendee).collect()
+    }
+
+    /// Kicks off attendee paging on a background thread so the event loop
+    /// stays responsive while a large event pages through; `poll_attendees_load`
+    /// drains its progress/result each tick. Fetches its own access token
+    /// (refreshing and retrying once, transparently, on a 401) from a clone
+    /// of the active account rather than touching `self.config` from the
+    /// background thread; `poll_attendees_load` merges any refreshed token
+    /// back once the thread finishes.
+    pub fn load_attendees(&mut self) {
+        debug!("Attempting to load attendees");
+
+        let account = match self.config.accounts.active_account() {
+            Some(account) => account.clone(),
+            None => {
+                self.apply_attendees_result(Err(crate::eventbrite_attendees::EventbriteError::Http {
+                    status: 0,
+                    body: "No Eventbrite account configured".to_string(),
+                }));
+                return;
+            }
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.loading_rx = Some(rx);
+        self.loading_progress = Some((0, 0));
+
+        std::thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let (account, result) = fetch_attendees_with_token_retry(account, move |loaded, total| {
+                let _ = progress_tx.send(AttendeesLoadEvent::Progress(loaded, total));
+            });
+            let _ = tx.send(AttendeesLoadEvent::Done(account, result));
+        });
+    }
+
+    /// Drains progress and completion events from an in-flight `load_attendees`
+    /// call. Called once per tick from the main loop; a no-op when no load is
+    /// in progress.
+    pub fn poll_attendees_load(&mut self) {
+        let rx = match &self.loading_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        let mut finished = None;
+        for event in rx.try_iter() {
+            match event {
+                AttendeesLoadEvent::Progress(loaded, total) => {
+                    self.loading_progress = Some((loaded, total));
+                }
+                AttendeesLoadEvent::Done(account, result) => finished = Some((account, result)),
+            }
+        }
+
+        if let Some((account, result)) = finished {
+            self.loading_rx = None;
+            self.loading_progress = None;
+            self.apply_refreshed_account(account);
+            self.apply_attendees_result(result);
+        }
+    }
+
+    /// Merges a background thread's `Account` (after a token fetch/refresh)
+    /// back into the cached `self.config`, persisting it only if the token
+    /// actually changed. Background threads (`load_attendees`,
+    /// `reconcile_checkins`) only ever work on a cloned `Account` in memory
+    /// and never call `load_config`/`save_config` themselves, since
+    /// decrypting/encrypting the config can block on an interactive
+    /// passphrase prompt — that must only ever happen here, on the main
+    /// thread, and only when there's actually something new to save.
+    fn apply_refreshed_account(&mut self, account: crate::eventbrite_auth::Account) {
+        let previous_created_at = self
+            .config
+            .accounts
+            .active_account()
+            .and_then(|a| a.token_created_at());
+        let changed = account.token_created_at() != previous_created_at;
+
+        if let Some(active) = self.config.accounts.active_account_mut() {
+            *active = account;
+        }
+
+        if changed {
+            if let Err(err) = save_config(&self.config) {
+                error!("Failed to persist refreshed token: {}", err);
+            }
         }
     }
 
-    pub fn load_attendees(&mut self, token: &str) {
-        debug!("Attempting to load attendees with token: {}", token);
-        match crate::eventbrite_attendees::get_attendees_from_api(token) {
-            Ok((attendees, event_date)) => {
+    fn apply_attendees_result(
+        &mut self,
+        result: Result<(Vec<Attendee>, String, String, Option<u64>), crate::eventbrite_attendees::EventbriteError>,
+    ) {
+        use crate::eventbrite_attendees::EventbriteError;
+
+        match result {
+            Ok((mut attendees, event_date, event_id, cached_at)) => {
                 debug!("Successfully fetched {} attendees", attendees.len());
+                let pending = crate::checkin::pending_checkins();
+                if !pending.is_empty() {
+                    for attendee in &mut attendees {
+                        if let Some(&checked_in) = pending.get(&attendee.id) {
+                            attendee.checked_in = checked_in;
+                        }
+                    }
+                }
                 self.attendees = attendees;
                 self.event_date = Some(event_date);
+                self.event_id = Some(event_id);
+                self.attendees_cached_at = cached_at;
+                self.selected.clear();
+                self.status_message = cached_at.map(|_| {
+                    "Hors ligne : liste des participants issue du dernier cache local.".to_string()
+                });
             }
             Err(err) => {
                 error!("Failed to fetch attendees: {}", err);
+                self.status_message = Some(match err {
+                    EventbriteError::Auth => {
+                        "Session expirée, reconnectez-vous dans Paramétrage.".to_string()
+                    }
+                    EventbriteError::RateLimited { retry_after } => {
+                        format!("Limite de requêtes atteinte, réessayez dans {}s.", retry_after)
+                    }
+                    other => format!("Échec du chargement des participants : {}", other),
+                });
             }
         }
     }
 
+    /// Whether `:` should open the command palette from the current view.
+    /// Excludes views with their own free-text input (popups, the name
+    /// search fields, the attendee filter) so typing a literal `:` there
+    /// keeps working.
+    fn accepts_command_palette(&self) -> bool {
+        match self.view {
+            AppView::MainMenu
+            | AppView::SettingsMenu
+            | AppView::AccountsMenu
+            | AppView::InspectorMenu
+            | AppView::ThemeMenu => true,
+            AppView::ListNextEventAttendeesMenu => !self.filtering,
+            _ => false,
+        }
+    }
+
     pub fn handle_input(&mut self, key: KeyCode) -> Option<String> {
         debug!("Handling input: {:?}", key);
+
+        if key == KeyCode::Char(':') && self.accepts_command_palette() {
+            debug!("Opening command palette");
+            self.view_before_command = self.view;
+            self.command_input.clear();
+            self.view = AppView::CommandMode;
+            return None;
+        }
+
         match self.view {
+            AppView::CommandMode => match key {
+                KeyCode::Esc => {
+                    self.command_input.clear();
+                    self.view = self.view_before_command;
+                }
+                KeyCode::Enter => {
+                    let line = self.command_input.clone();
+                    self.command_input.clear();
+                    self.view = self.view_before_command;
+                    return self.execute_command(&line);
+                }
+                KeyCode::Char(c) => {
+                    self.command_input.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.command_input.pop();
+                }
+                _ => {}
+            },
             AppView::MainMenu => match key {
                 KeyCode::Esc => {
                     debug!("Quit selected in MainMenu");
                     return Some("quit".to_string());
                 }
                 KeyCode::Down => {
-                    self.main_menu_index = (self.main_menu_index + 1) % 3;
+                    self.main_menu_index = (self.main_menu_index + 1) % 4;
                     debug!("MainMenu index changed to {}", self.main_menu_index);
                 }
                 KeyCode::Up => {
                     self.main_menu_index = if self.main_menu_index == 0 {
-                        2
+                        3
                     } else {
                         self.main_menu_index - 1
                     };
@@ -98,15 +390,7 @@ impl App {
                     0 => {
                         debug!("Navigating to ListNextEventAttendeesMenu");
                         self.view = AppView::ListNextEventAttendeesMenu;
-                        match crate::eventbrite_auth::get_access_token() {
-                            Ok(token) => {
-                                debug!("Access token retrieved: {}", token);
-                                self.load_attendees(&token);
-                            }
-                            Err(err) => {
-                                error!("Failed to retrieve access token: {}", err);
-                            }
-                        }
+                        self.load_attendees();
                     }
                     1 => {
                         debug!("Navigating to SettingsMenu");
@@ -117,12 +401,52 @@ impl App {
                         self.view = AppView::FindByNameMenu;
                         self.name_search_state = NameSearchState::default();
                     }
+                    3 => {
+                        debug!("Navigating to InspectorMenu");
+                        self.inspector_menu_index = 0;
+                        self.view = AppView::InspectorMenu;
+                    }
                     _ => {}
                 },
                 _ => {
                     debug!("Unhandled key in MainMenu: {:?}", key);
                 }
             },
+            AppView::InspectorMenu => match key {
+                KeyCode::Esc => {
+                    self.view = AppView::MainMenu;
+                }
+                KeyCode::Up => {
+                    if self.inspector_menu_index > 0 {
+                        self.inspector_menu_index -= 1;
+                    }
+                }
+                KeyCode::Down => {
+                    let len = crate::inspector::INSPECTOR.entries().len();
+                    if len > 0 && self.inspector_menu_index < len - 1 {
+                        self.inspector_menu_index += 1;
+                    }
+                }
+                _ => {}
+            },
+            AppView::ListNextEventAttendeesMenu if self.filtering => match key {
+                KeyCode::Esc | KeyCode::Enter => {
+                    self.filtering = false;
+                    self.selected_row = 0;
+                    self.selected.clear();
+                }
+                KeyCode::Char(c) => {
+                    self.attendee_filter.push(c);
+                    self.selected_row = 0;
+                    self.selected.clear();
+                }
+                KeyCode::Backspace => {
+                    self.attendee_filter.pop();
+                    self.selected_row = 0;
+                    self.selected.clear();
+                }
+                _ => {}
+            },
             AppView::ListNextEventAttendeesMenu => match key {
                 KeyCode::Esc => {
                     self.view = AppView::MainMenu;
@@ -133,7 +457,8 @@ impl App {
                     }
                 }
                 KeyCode::Down => {
-                    if self.selected_row < self.attendees.len() - 1 {
+                    let visible = self.filtered_attendees().len();
+                    if visible > 0 && self.selected_row < visible - 1 {
                         self.selected_row += 1;
                     }
                 }
@@ -143,10 +468,38 @@ impl App {
                     }
                 }
                 KeyCode::Right => {
-                    if self.selected_col < 6 {
+                    if self.selected_col < 7 {
                         self.selected_col += 1;
                     }
                 }
+                KeyCode::Char('/') => {
+                    self.filtering = true;
+                    self.attendee_filter.clear();
+                    self.selected_row = 0;
+                    self.selected.clear();
+                }
+                KeyCode::Char('r') => {
+                    debug!("Refreshing attendees from API");
+                    self.load_attendees();
+                }
+                KeyCode::Enter => {
+                    self.toggle_checkin_selected();
+                }
+                KeyCode::Char(' ') => {
+                    self.toggle_row_selected();
+                }
+                KeyCode::Char('a') => {
+                    self.select_all_visible();
+                }
+                KeyCode::Char('A') => {
+                    self.selected.clear();
+                }
+                KeyCode::Char('y') => {
+                    self.copy_selected_attendees();
+                }
+                KeyCode::Char('e') => {
+                    self.open_export_popup();
+                }
                 KeyCode::Char('c') => {
                     let value = self.get_selected_cell_value();
                     debug!("Copied value: {}", value);
@@ -165,12 +518,12 @@ impl App {
                     self.view = AppView::MainMenu;
                 }
                 KeyCode::Down => {
-                    self.settings_menu_index = (self.settings_menu_index + 1) % 2;
+                    self.settings_menu_index = (self.settings_menu_index + 1) % 4;
                     debug!("SettingsMenu index changed to {}", self.settings_menu_index);
                 }
                 KeyCode::Up => {
                     self.settings_menu_index = if self.settings_menu_index == 0 {
-                        2
+                        3
                     } else {
                         self.settings_menu_index - 1
                     };
@@ -188,8 +541,16 @@ impl App {
                         self.input_buffer.clear();
                     }
                     2 => {
-                        debug!("Returning to MainMenu from SettingsMenu");
-                        self.view = AppView::MainMenu;
+                        debug!("Navigating to AccountsMenu");
+                        self.accounts_menu_index = 0;
+                        self.view = AppView::AccountsMenu;
+                    }
+                    3 => {
+                        debug!("Navigating to ThemeMenu");
+                        let active = self.config.theme_name;
+                        self.theme_menu_index =
+                            crate::theme::ThemeName::ALL.iter().position(|t| *t == active).unwrap_or(0);
+                        self.view = AppView::ThemeMenu;
                     }
                     _ => {
                         debug!("Unhandled SettingsMenu index: {}", self.settings_menu_index);
@@ -199,6 +560,33 @@ impl App {
                     debug!("Unhandled key in SettingsMenu: {:?}", key);
                 }
             },
+            AppView::ThemeMenu => match key {
+                KeyCode::Esc => {
+                    self.view = AppView::SettingsMenu;
+                }
+                KeyCode::Down => {
+                    self.theme_menu_index = (self.theme_menu_index + 1) % crate::theme::ThemeName::ALL.len();
+                }
+                KeyCode::Up => {
+                    self.theme_menu_index = if self.theme_menu_index == 0 {
+                        crate::theme::ThemeName::ALL.len() - 1
+                    } else {
+                        self.theme_menu_index - 1
+                    };
+                }
+                KeyCode::Enter => {
+                    let theme_name = crate::theme::ThemeName::ALL[self.theme_menu_index];
+                    self.config.theme_name = theme_name;
+                    save_config(&self.config).expect("Failed to save configuration");
+                    self.theme = theme_name.resolve();
+                    debug!("Theme changed to {:?}", theme_name);
+                    self.status_message = Some(format!("Thème « {} » activé.", theme_name.label()));
+                    self.view = AppView::SettingsMenu;
+                }
+                _ => {
+                    debug!("Unhandled key in ThemeMenu: {:?}", key);
+                }
+            },
             AppView::SetClientIdPopup => match key {
                 KeyCode::Esc => {
                     debug!("Exiting SetClientIdPopup, returning to SettingsMenu");
@@ -206,9 +594,10 @@ impl App {
                 }
                 KeyCode::Enter => {
                     debug!("Saving CLIENT_ID: {}", self.input_buffer.trim());
-                    let mut config = load_config().unwrap_or_default();
-                    config.client_id = Some(self.input_buffer.trim().to_string());
-                    save_config(&config).expect("Failed to save configuration");
+                    if let Some(account) = self.config.accounts.active_account_mut() {
+                        account.client_id = Some(self.input_buffer.trim().to_string());
+                        save_config(&self.config).expect("Failed to save configuration");
+                    }
                     self.view = AppView::SettingsMenu;
                 }
                 KeyCode::Char(c) => {
@@ -230,9 +619,11 @@ impl App {
                 }
                 KeyCode::Enter => {
                     debug!("Saving CLIENT_SECRET: {}", self.input_buffer.trim());
-                    let mut config = load_config().unwrap_or_default();
-                    config.client_secret = Some(self.input_buffer.trim().to_string());
-                    save_config(&config).expect("Failed to save configuration");
+                    if let Some(account) = self.config.accounts.active_account_mut() {
+                        account.client_secret =
+                            Some(secrecy::SecretString::new(self.input_buffer.trim().to_string()));
+                        save_config(&self.config).expect("Failed to save configuration");
+                    }
                     self.view = AppView::SettingsMenu;
                 }
                 KeyCode::Char(c) => {
@@ -247,6 +638,68 @@ impl App {
                     debug!("Unhandled key in SetClientSecretPopup: {:?}", key);
                 }
             },
+            AppView::ExportPopup => match key {
+                KeyCode::Esc => {
+                    self.view = self.view_before_export;
+                }
+                KeyCode::Enter => {
+                    self.export_attendees_to(self.input_buffer.trim().to_string());
+                    self.view = self.view_before_export;
+                }
+                KeyCode::Char(c) => {
+                    self.input_buffer.push(c);
+                }
+                KeyCode::Backspace => {
+                    self.input_buffer.pop();
+                }
+                _ => {}
+            },
+            AppView::AccountsMenu => match key {
+                KeyCode::Esc => {
+                    self.view = AppView::SettingsMenu;
+                }
+                KeyCode::Up => {
+                    let len = self.config.accounts.accounts.len() + 1; // +1 for "add account"
+                    self.accounts_menu_index = if self.accounts_menu_index == 0 {
+                        len - 1
+                    } else {
+                        self.accounts_menu_index - 1
+                    };
+                }
+                KeyCode::Down => {
+                    let len = self.config.accounts.accounts.len() + 1;
+                    self.accounts_menu_index = (self.accounts_menu_index + 1) % len;
+                }
+                KeyCode::Enter => {
+                    if self.accounts_menu_index < self.config.accounts.accounts.len() {
+                        debug!("Activating account at index {}", self.accounts_menu_index);
+                        self.config.accounts.active = self.accounts_menu_index;
+                        save_config(&self.config).expect("Failed to save configuration");
+                    } else {
+                        debug!("Adding a new account");
+                        let name = format!("Compte {}", self.config.accounts.accounts.len() + 1);
+                        self.config.accounts.add_account(name);
+                        save_config(&self.config).expect("Failed to save configuration");
+                        self.input_buffer.clear();
+                        self.view = AppView::SetClientIdPopup;
+                    }
+                }
+                KeyCode::Char('d') => {
+                    if self.accounts_menu_index < self.config.accounts.accounts.len()
+                        && self.config.accounts.accounts.len() > 1
+                    {
+                        debug!("Removing account at index {}", self.accounts_menu_index);
+                        self.config.accounts.remove_account(self.accounts_menu_index);
+                        save_config(&self.config).expect("Failed to save configuration");
+                        if self.accounts_menu_index >= self.config.accounts.accounts.len() {
+                            self.accounts_menu_index = self.config.accounts.accounts.len().saturating_sub(1);
+                        }
+                    }
+                }
+                _ => {
+                    debug!("Unhandled key in AccountsMenu: {:?}", key);
+                }
+            },
             AppView::FindByNameMenu => match key {
                 KeyCode::Esc => {
                     self.view = AppView::MainMenu;
@@ -266,10 +719,10 @@ impl App {
                     if !self.name_search_state.input_first_name.trim().is_empty()
                         && !self.name_search_state.input_last_name.trim().is_empty()
                     {
-                        self.name_search_state.results = Some(self.find_events_by_name(
-                            &self.name_search_state.input_first_name,
-                            &self.name_search_state.input_last_name,
-                        ));
+                        let first_name = self.name_search_state.input_first_name.clone();
+                        let last_name = self.name_search_state.input_last_name.clone();
+                        let results = self.find_events_by_name(&first_name, &last_name);
+                        self.name_search_state.results = Some(results);
                         self.name_search_state.results_scroll = 0; // reset scroll
                     }
                 }
@@ -312,11 +765,12 @@ impl App {
     }
 
     fn get_selected_cell_value(&self) -> String {
-        if self.selected_row >= self.attendees.len() {
+        let attendees = self.filtered_attendees();
+        if self.selected_row >= attendees.len() {
             return String::new();
         }
 
-        let attendee = &self.attendees[self.selected_row];
+        let attendee = attendees[self.selected_row];
         match self.selected_col {
             0 => attendee.profile.first_name.clone().unwrap_or_default(),
             1 => attendee.profile.last_name.clone().unwrap_or_default(),
@@ -324,70 +778,434 @@ impl App {
             3 => attendee.profile.cell_phone.clone().unwrap_or_default(),
             4 => attendee.ticket_class_name.clone().unwrap_or_default(),
             5 => attendee.created.clone(),
+            7 => if attendee.checked_in { "Oui".to_string() } else { "Non".to_string() },
             _ => String::new(),
         }
     }
 
-    pub fn find_events_by_name(&self, first_name: &str, last_name: &str) -> Vec<(String, String)> {
-        use reqwest::blocking::Client;
+    /// Toggles whether the currently selected row is marked in `selected`
+    /// for a bulk action.
+    fn toggle_row_selected(&mut self) {
+        if !self.selected.insert(self.selected_row) {
+            self.selected.remove(&self.selected_row);
+        }
+    }
+
+    fn select_all_visible(&mut self) {
+        self.selected = (0..self.filtered_attendees().len()).collect();
+    }
+
+    /// Copies every selected attendee (all columns, tab-separated, one row
+    /// per line) to the clipboard, mirroring the single-cell `'c'` copy.
+    fn copy_selected_attendees(&mut self) {
+        if self.selected.is_empty() {
+            self.status_message = Some("Aucun participant sélectionné.".to_string());
+            return;
+        }
+
+        let visible = self.filtered_attendees();
+        let lines: Vec<String> = self
+            .selected
+            .iter()
+            .filter_map(|&row| visible.get(row))
+            .map(|attendee| {
+                [
+                    attendee.profile.first_name.clone().unwrap_or_default(),
+                    attendee.profile.last_name.clone().unwrap_or_default(),
+                    attendee.profile.email.clone().unwrap_or_default(),
+                    attendee.profile.cell_phone.clone().unwrap_or_default(),
+                    attendee.birthdate.clone().unwrap_or_default(),
+                    attendee.ticket_class_name.clone().unwrap_or_default(),
+                    attendee.created.clone(),
+                    if attendee.checked_in { "Oui" } else { "Non" }.to_string(),
+                ]
+                .join("\t")
+            })
+            .collect();
+
+        if let Err(err) =
+            Clipboard::new().and_then(|mut clipboard| clipboard.set_text(lines.join("\n")))
+        {
+            error!("Failed to copy selection to clipboard: {}", err);
+            self.status_message = Some("Échec de la copie dans le presse-papiers.".to_string());
+        } else {
+            debug!("Copied {} selected attendees to clipboard", lines.len());
+            self.status_message = Some(format!("{} participants copiés.", lines.len()));
+        }
+    }
+
+    /// Opens `AppView::ExportPopup` with a filename suggested from
+    /// `event_date`, reusing `input_buffer` like the other text-entry popups.
+    fn open_export_popup(&mut self) {
+        self.input_buffer = self.default_export_filename();
+        self.view_before_export = self.view;
+        self.view = AppView::ExportPopup;
+    }
+
+    fn default_export_filename(&self) -> String {
+        match &self.event_date {
+            Some(date) if !date.is_empty() => format!("participants_{}.csv", date.replace('/', "-")),
+            _ => "participants.csv".to_string(),
+        }
+    }
+
+    fn export_attendees_to(&mut self, path: String) {
+        if path.is_empty() {
+            self.status_message = Some("Chemin d'export vide, export annulé.".to_string());
+            return;
+        }
+
+        match crate::export::export_attendees(&self.attendees, &path) {
+            Ok(()) => {
+                debug!("Exported {} attendees to {}", self.attendees.len(), path);
+                self.status_message = Some(format!("{} participants exportés vers {}.", self.attendees.len(), path));
+            }
+            Err(err) => {
+                error!("Failed to export attendees to {}: {}", path, err);
+                self.status_message = Some(format!("Échec de l'export : {}", err));
+            }
+        }
+    }
+
+    /// Toggles `checked_in` for the currently selected row, persists it
+    /// locally (so it survives a restart), and starts a background sync to
+    /// Eventbrite via `reconcile_checkins` — a failed sync stays pending for
+    /// the next reconciliation.
+    fn toggle_checkin_selected(&mut self) {
+        let attendee_id = match self.filtered_attendees().get(self.selected_row) {
+            Some(attendee) => attendee.id.clone(),
+            None => return,
+        };
+
+        let checked_in = match self.attendees.iter_mut().find(|a| a.id == attendee_id) {
+            Some(attendee) => {
+                attendee.checked_in = !attendee.checked_in;
+                attendee.checked_in
+            }
+            None => return,
+        };
+
+        debug!("Toggled check-in for attendee {} -> {}", attendee_id, checked_in);
+        if let Err(err) = crate::checkin::set_checked_in(&attendee_id, checked_in) {
+            error!("Failed to persist check-in state: {}", err);
+        }
+
+        self.reconcile_checkins();
+    }
+
+    /// Pushes any locally recorded check-ins to Eventbrite on a background
+    /// thread, since `checkin::reconcile` makes blocking HTTP calls (with
+    /// rate-limit backoff) that would otherwise freeze keystrokes and
+    /// redraws for volunteers checking people in at the door. Safe to call
+    /// opportunistically (on toggle, or periodically from `run_app`): a
+    /// no-op when there's nothing pending, the API is unreachable, or a
+    /// previous reconciliation is still in flight. `poll_reconcile_checkins`
+    /// picks up the result each tick, mirroring `load_attendees`/
+    /// `poll_attendees_load`.
+    pub fn reconcile_checkins(&mut self) {
+        if self.reconcile_rx.is_some() {
+            return;
+        }
+
+        let event_id = match self.event_id.clone() {
+            Some(event_id) => event_id,
+            None => return,
+        };
+
+        let account = match self.config.accounts.active_account() {
+            Some(account) => account.clone(),
+            None => return,
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.reconcile_rx = Some(rx);
+
+        std::thread::spawn(move || {
+            let mut account = account;
+            let token = match crate::eventbrite_auth::get_access_token(&mut account, false) {
+                Ok(token) => token,
+                Err(_) => {
+                    let _ = tx.send((account, 0, 0));
+                    return;
+                }
+            };
+
+            let (synced, pending) = crate::checkin::reconcile(&token, &event_id);
+            let _ = tx.send((account, synced, pending));
+        });
+    }
+
+    /// Drains the result of an in-flight `reconcile_checkins` call. Called
+    /// once per tick from the main loop; a no-op when no reconciliation is
+    /// in progress or none has completed yet.
+    pub fn poll_reconcile_checkins(&mut self) {
+        let rx = match &self.reconcile_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        if let Ok((account, synced, pending)) = rx.try_recv() {
+            self.reconcile_rx = None;
+            self.apply_refreshed_account(account);
+            if synced > 0 || pending > 0 {
+                debug!("Check-in reconciliation: {} synced, {} pending", synced, pending);
+            }
+        }
+    }
+
+    /// Parses and executes a command-palette line. Returns `Some("quit")`
+    /// for the `quit` action, mirroring `handle_input`'s own contract;
+    /// every other action mutates state directly and returns `None`.
+    fn execute_command(&mut self, line: &str) -> Option<String> {
+        match crate::command::parse(line) {
+            Ok(action) => self.dispatch_action(action),
+            Err(err) => {
+                // Never log the raw line: `set client_secret <value>` carries
+                // the secret itself in `line`, and a parse failure (e.g. a
+                // missing quote) would otherwise write it straight to the log.
+                let verb = line.split_whitespace().next().unwrap_or("");
+                error!("Failed to parse command (verb: '{}'): {}", verb, err);
+                self.status_message = Some(format!("Commande invalide : {}", err));
+                None
+            }
+        }
+    }
+
+    fn dispatch_action(&mut self, action: crate::command::Action) -> Option<String> {
+        use crate::command::Action;
+
+        match action {
+            Action::Quit => return Some("quit".to_string()),
+            Action::Goto { target } => self.goto(&target),
+            Action::Find { first_name, last_name } => {
+                let results = self.find_events_by_name(&first_name, &last_name);
+                self.name_search_state = NameSearchState::default();
+                self.name_search_state.input_first_name = first_name;
+                self.name_search_state.input_last_name = last_name;
+                self.name_search_state.results = Some(results);
+                self.view = AppView::FindByNameMenu;
+            }
+            Action::Copy { field } => self.copy_field(&field),
+            Action::SetClientId(value) => {
+                self.set_account_field(|account| account.client_id = Some(value));
+            }
+            Action::SetClientSecret(value) => {
+                self.set_account_field(|account| {
+                    account.client_secret = Some(secrecy::SecretString::new(value));
+                });
+            }
+            Action::ExportCsv => {
+                self.open_export_popup();
+            }
+        }
+        None
+    }
+
+    fn goto(&mut self, target: &str) {
+        match target {
+            "main" | "mainmenu" => self.view = AppView::MainMenu,
+            "settings" => self.view = AppView::SettingsMenu,
+            "find" => {
+                self.name_search_state = NameSearchState::default();
+                self.view = AppView::FindByNameMenu;
+            }
+            "accounts" => self.view = AppView::AccountsMenu,
+            "inspector" => {
+                self.inspector_menu_index = 0;
+                self.view = AppView::InspectorMenu;
+            }
+            "attendees" | "list" => {
+                self.view = AppView::ListNextEventAttendeesMenu;
+                self.load_attendees();
+            }
+            other => {
+                self.status_message = Some(format!("Vue inconnue : {}", other));
+            }
+        }
+    }
+
+    fn copy_field(&mut self, field: &str) {
+        let attendees = self.filtered_attendees();
+        let attendee = match attendees.get(self.selected_row) {
+            Some(attendee) => attendee,
+            None => {
+                self.status_message = Some("Aucun participant sélectionné.".to_string());
+                return;
+            }
+        };
+
+        let value = match field {
+            "first_name" | "prenom" => attendee.profile.first_name.clone().unwrap_or_default(),
+            "last_name" | "nom" => attendee.profile.last_name.clone().unwrap_or_default(),
+            "email" => attendee.profile.email.clone().unwrap_or_default(),
+            "phone" | "telephone" => attendee.profile.cell_phone.clone().unwrap_or_default(),
+            "birthdate" => attendee.birthdate.clone().unwrap_or_default(),
+            "ticket_class" => attendee.ticket_class_name.clone().unwrap_or_default(),
+            "created" => attendee.created.clone(),
+            other => {
+                self.status_message = Some(format!("Champ inconnu : {}", other));
+                return;
+            }
+        };
+
+        if let Err(err) = Clipboard::new().and_then(|mut clipboard| clipboard.set_text(value)) {
+            error!("Failed to copy to clipboard: {}", err);
+            self.status_message = Some("Échec de la copie dans le presse-papiers.".to_string());
+        } else {
+            debug!("Value successfully copied to clipboard via command palette");
+            self.status_message = Some(format!("{} copié.", field));
+        }
+    }
+
+    fn set_account_field(&mut self, mutate: impl FnOnce(&mut crate::eventbrite_auth::Account)) {
+        match self.config.accounts.active_account_mut() {
+            Some(account) => {
+                mutate(account);
+                save_config(&self.config).expect("Failed to save configuration");
+                self.status_message = Some("Configuration mise à jour.".to_string());
+            }
+            None => {
+                self.status_message = Some("Aucun compte actif.".to_string());
+            }
+        }
+    }
 
-        let mut found_events = Vec::new();
-        let token = match crate::eventbrite_auth::get_access_token() {
+    /// Searches every event the active account can see for attendees whose
+    /// name fuzzily matches `first_name`/`last_name`, ranked best first.
+    /// Silently retries once after a transparent token refresh if the
+    /// cached access token was rejected; any other failure returns an
+    /// empty result, matching this search's existing fail-quiet behavior.
+    /// Runs on the main thread (called directly from a key handler, never
+    /// from a `draw_*` function), so it's fine for it to go through
+    /// `self.config` and persist a refreshed token synchronously.
+    pub fn find_events_by_name(&mut self, first_name: &str, last_name: &str) -> Vec<(String, String)> {
+        use crate::eventbrite_attendees::EventbriteError;
+
+        let token = match self.access_token_for_active_account(false) {
             Ok(token) => token,
-            Err(_) => return found_events,
+            Err(_) => return Vec::new(),
         };
-        let client = Client::new();
 
-        // Fetch organization ID
-        let org_id = match crate::eventbrite_attendees::get_organization_id(&client, &token) {
-            Some(id) => id,
-            None => return found_events,
+        match Self::find_events_by_name_with_token(&token, first_name, last_name) {
+            Ok(events) => events,
+            Err(EventbriteError::Auth) => {
+                warn!("Access token rejected while searching by name, attempting a silent refresh");
+                match self.access_token_for_active_account(true) {
+                    Ok(token) => Self::find_events_by_name_with_token(&token, first_name, last_name)
+                        .unwrap_or_default(),
+                    Err(err) => {
+                        error!("Silent token refresh failed: {}", err);
+                        Vec::new()
+                    }
+                }
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Fetches (refreshing if `force_refresh`, or if the cache is stale) an
+    /// access token for the active account, persisting it back to
+    /// `self.config` in place if the token actually changed. Only safe to
+    /// call from the main thread: persisting can block on an interactive
+    /// passphrase prompt when the config is encrypted.
+    fn access_token_for_active_account(&mut self, force_refresh: bool) -> anyhow::Result<String> {
+        let previous_created_at = self
+            .config
+            .accounts
+            .active_account()
+            .and_then(|a| a.token_created_at());
+
+        let token = {
+            let account = self
+                .config
+                .accounts
+                .active_account_mut()
+                .ok_or_else(|| anyhow::anyhow!("No Eventbrite account configured"))?;
+            crate::eventbrite_auth::get_access_token(account, force_refresh)?
         };
 
+        let changed = self
+            .config
+            .accounts
+            .active_account()
+            .and_then(|a| a.token_created_at())
+            != previous_created_at;
+        if changed {
+            if let Err(err) = save_config(&self.config) {
+                error!("Failed to persist refreshed token: {}", err);
+            }
+        }
+
+        Ok(token)
+    }
+
+    fn find_events_by_name_with_token(
+        token: &str,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Vec<(String, String)>, crate::eventbrite_attendees::EventbriteError> {
+        use crate::eventbrite_attendees::{classify_error, retry_after_seconds, EventbriteError};
+        use reqwest::blocking::Client;
+
+        let client = Client::new();
+
+        let org_id = crate::eventbrite_attendees::get_organization_id(&client, token)?
+            .ok_or_else(|| EventbriteError::Http {
+                status: 0,
+                body: "No organization found for this account".to_string(),
+            })?;
+
         // Fetch all events (not just next)
-        let res = client
+        let resp = client
             .get(&format!(
                 "https://www.eventbriteapi.com/v3/organizations/{}/events/",
                 org_id
             ))
-            .bearer_auth(&token)
+            .bearer_auth(token)
             .query(&[("order_by", "start_desc"), ("status", "completed,live")])
-            .send();
+            .send()?;
 
-        let events: Vec<crate::eventbrite_attendees::Event> = match res {
-            Ok(resp) => {
-                if !resp.status().is_success() {
-                    return found_events;
-                }
-                match resp.json::<crate::eventbrite_attendees::EventsResponse>() {
-                    Ok(data) => data.events,
-                    Err(_) => return found_events,
-                }
-            }
-            Err(_) => return found_events,
-        };
+        let status = resp.status();
+        if !status.is_success() {
+            let retry_after = retry_after_seconds(&resp);
+            let body = resp.text().unwrap_or_default();
+            return Err(classify_error(status, retry_after, body));
+        }
+        let events: Vec<crate::eventbrite_attendees::Event> =
+            resp.json::<crate::eventbrite_attendees::EventsResponse>()?.events;
+
+        let query = format!("{} {}", first_name.trim(), last_name.trim());
+        let mut scored_events: Vec<(f64, (String, String))> = Vec::new();
 
         for event in events {
-            let attendees = crate::eventbrite_attendees::get_attendees(&client, &token, &event.id);
-            for attendee in attendees {
-                let matches_first = attendee
-                    .profile
-                    .first_name
-                    .as_ref()
-                    .map(|n| n.eq_ignore_ascii_case(first_name.trim()))
-                    .unwrap_or(false);
-                let matches_last = attendee
-                    .profile
-                    .last_name
-                    .as_ref()
-                    .map(|n| n.eq_ignore_ascii_case(last_name.trim()))
-                    .unwrap_or(false);
-                if matches_first && matches_last {
-                    found_events.push((event.name.text.clone(), event.start.local.clone()));
-                    break;
-                }
+            let attendees = match crate::eventbrite_attendees::get_attendees(
+                &client,
+                token,
+                &event.id,
+                |_, _| {},
+            ) {
+                Ok(attendees) => attendees,
+                Err(EventbriteError::Auth) => return Err(EventbriteError::Auth),
+                Err(_) => continue,
+            };
+            let best_score = attendees
+                .iter()
+                .filter_map(|attendee| {
+                    let full_name = format!(
+                        "{} {}",
+                        attendee.profile.first_name.as_deref().unwrap_or(""),
+                        attendee.profile.last_name.as_deref().unwrap_or("")
+                    );
+                    crate::fuzzy::subsequence_score(&query, &full_name)
+                })
+                .max_by(|a, b| a.total_cmp(b));
+
+            if let Some(score) = best_score {
+                scored_events.push((score, (event.name.text.clone(), event.start.local.clone())));
             }
         }
-        found_events
+
+        scored_events.sort_by(|a, b| b.0.total_cmp(&a.0));
+        Ok(scored_events.into_iter().map(|(_, event)| event).collect())
     }
 }
@@ -0,0 +1,57 @@
+//! On-disk fallback for the attendee list, so check-in at the door keeps
+//! working when venue WiFi drops. Written after every successful API fetch
+//! and read back by `eventbrite_attendees::get_attendees_from_api` when the
+//! API call fails with a network or rate-limit error.
+
+use crate::eventbrite_attendees::Attendee;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CACHE_FILE: &str = ".les_ptits_gilets_cache.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AttendeeCache {
+    pub event_id: String,
+    pub event_name: String,
+    pub event_date: String,
+    pub attendees: Vec<Attendee>,
+    pub fetched_at: u64,
+}
+
+fn get_cache_path() -> PathBuf {
+    dirs::home_dir()
+        .expect("Unable to find home directory")
+        .join(CACHE_FILE)
+}
+
+/// Writes `cache` to disk, stamping `fetched_at` with the current time.
+pub fn save_cache(event_id: &str, event_name: &str, event_date: &str, attendees: &[Attendee]) -> Result<AttendeeCache> {
+    let fetched_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let cache = AttendeeCache {
+        event_id: event_id.to_string(),
+        event_name: event_name.to_string(),
+        event_date: event_date.to_string(),
+        attendees: attendees.to_vec(),
+        fetched_at,
+    };
+
+    let data = serde_json::to_string_pretty(&cache)?;
+    fs::write(get_cache_path(), data)?;
+    Ok(cache)
+}
+
+/// Best-effort read of the last successfully fetched attendee list. Returns
+/// `None` if no cache exists yet or the file is unreadable/corrupt — callers
+/// treat that the same as "no fallback available".
+pub fn load_cache() -> Option<AttendeeCache> {
+    let path = get_cache_path();
+    if !path.exists() {
+        return None;
+    }
+
+    let data = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
@@ -0,0 +1,164 @@
+//! Local check-in state for attendees, synced back to Eventbrite so two
+//! staff laptops checking in the same person converge instead of double
+//! counting. A write is an idempotent set keyed by attendee id — each
+//! write replaces that attendee's record, there's no increment — and
+//! survives restarts;
+//! anything that couldn't reach the API yet stays pending and is retried
+//! by `reconcile`.
+
+use crate::eventbrite_attendees::EventbriteError;
+use anyhow::Result;
+use log::{debug, error, warn};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const CHECKINS_FILE: &str = ".les_ptits_gilets_checkins.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckinRecord {
+    pub checked_in: bool,
+    pub updated_at: u64,
+    #[serde(default)]
+    pub synced: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CheckinStore {
+    pub records: HashMap<String, CheckinRecord>,
+}
+
+fn get_store_path() -> PathBuf {
+    dirs::home_dir()
+        .expect("Unable to find home directory")
+        .join(CHECKINS_FILE)
+}
+
+fn load_store() -> CheckinStore {
+    let path = get_store_path();
+    if !path.exists() {
+        return CheckinStore::default();
+    }
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_store(store: &CheckinStore) -> Result<()> {
+    let data = serde_json::to_string_pretty(store)?;
+    fs::write(get_store_path(), data)?;
+    Ok(())
+}
+
+/// Idempotently records `checked_in` for `attendee_id` and marks it pending
+/// sync, so it's retried by `reconcile` until Eventbrite confirms it.
+pub fn set_checked_in(attendee_id: &str, checked_in: bool) -> Result<()> {
+    let mut store = load_store();
+    let updated_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    store.records.insert(
+        attendee_id.to_string(),
+        CheckinRecord {
+            checked_in,
+            updated_at,
+            synced: false,
+        },
+    );
+    save_store(&store)
+}
+
+/// Returns the `checked_in` value locally recorded for every attendee that
+/// hasn't synced to Eventbrite yet, keyed by attendee id. Callers installing
+/// a freshly fetched attendee list (API or cache) should overlay these so a
+/// check-in toggled just before a refresh races ahead of `reconcile` doesn't
+/// appear to silently revert.
+pub fn pending_checkins() -> HashMap<String, bool> {
+    load_store()
+        .records
+        .into_iter()
+        .filter(|(_, record)| !record.synced)
+        .map(|(attendee_id, record)| (attendee_id, record.checked_in))
+        .collect()
+}
+
+/// Pushes every unsynced check-in to Eventbrite, marking each as synced on
+/// success and leaving failures pending for the next call. Returns
+/// `(synced_count, pending_count)`.
+pub fn reconcile(token: &str, event_id: &str) -> (usize, usize) {
+    let mut store = load_store();
+    let pending: Vec<(String, CheckinRecord)> = store
+        .records
+        .iter()
+        .filter(|(_, record)| !record.synced)
+        .map(|(attendee_id, record)| (attendee_id.clone(), record.clone()))
+        .collect();
+
+    if pending.is_empty() {
+        return (0, 0);
+    }
+
+    let client = Client::new();
+    let mut synced_count = 0;
+
+    for (attendee_id, record) in &pending {
+        match push_checkin(&client, token, event_id, attendee_id, record.checked_in) {
+            Ok(()) => {
+                debug!("Synced check-in for attendee {}", attendee_id);
+                if let Some(stored) = store.records.get_mut(attendee_id) {
+                    stored.synced = true;
+                }
+                synced_count += 1;
+            }
+            Err(err) => {
+                warn!("Failed to sync check-in for attendee {}: {}", attendee_id, err);
+            }
+        }
+    }
+
+    if let Err(err) = save_store(&store) {
+        error!("Failed to persist check-in store after reconciliation: {}", err);
+    }
+
+    (synced_count, pending.len() - synced_count)
+}
+
+fn push_checkin(
+    client: &Client,
+    token: &str,
+    event_id: &str,
+    attendee_id: &str,
+    checked_in: bool,
+) -> Result<(), EventbriteError> {
+    let url = format!(
+        "https://www.eventbriteapi.com/v3/events/{}/attendees/{}/",
+        event_id, attendee_id
+    );
+    let start = Instant::now();
+    let res = client
+        .post(&url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "attendee": { "checked_in": checked_in } }))
+        .send()?;
+
+    let status = res.status();
+    let retry_after = crate::eventbrite_attendees::retry_after_seconds(&res);
+    let body = res.text()?;
+    crate::inspector::record_request(
+        "POST",
+        &url,
+        crate::inspector::BEARER_HEADERS,
+        Some(status.as_u16()),
+        start.elapsed(),
+        &body,
+    );
+
+    if !status.is_success() {
+        return Err(crate::eventbrite_attendees::classify_error(status, retry_after, body));
+    }
+
+    Ok(())
+}
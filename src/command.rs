@@ -0,0 +1,137 @@
+//! Grammar for the `:`-prefixed command palette (`AppView::CommandMode`),
+//! in the spirit of meli's textual command line: a hand-written tokenizer
+//! with quoted-string support feeding a small set of typed `Action`s that
+//! `App` executes the same way its key handlers do today.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error("Commande vide")]
+    Empty,
+    #[error("Guillemet non terminé")]
+    UnterminatedQuote,
+    #[error("Commande inconnue : {0}")]
+    Unknown(String),
+    #[error("'{command}' attend {expected}")]
+    MissingArgument {
+        command: String,
+        expected: &'static str,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Find { first_name: String, last_name: String },
+    Copy { field: String },
+    Goto { target: String },
+    SetClientId(String),
+    SetClientSecret(String),
+    ExportCsv,
+    Quit,
+}
+
+/// Splits a command line into tokens on whitespace, treating a
+/// double-quoted span (`"John Doe"`) as a single token so names with
+/// spaces can be passed to `find`.
+fn tokenize(line: &str) -> Result<Vec<String>, CommandError> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '"' {
+            chars.next();
+            let mut token = String::new();
+            let mut terminated = false;
+            for c in chars.by_ref() {
+                if c == '"' {
+                    terminated = true;
+                    break;
+                }
+                token.push(c);
+            }
+            if !terminated {
+                return Err(CommandError::UnterminatedQuote);
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parses a command line (e.g. `find "John" "Doe"`, `copy email`,
+/// `goto settings`, `set client_id <value>`, `export csv`, `quit`) into an
+/// `Action`.
+pub fn parse(line: &str) -> Result<Action, CommandError> {
+    let mut tokens = tokenize(line)?.into_iter();
+    let command = tokens.next().ok_or(CommandError::Empty)?;
+
+    match command.as_str() {
+        "find" => {
+            let first_name = tokens.next().ok_or_else(|| CommandError::MissingArgument {
+                command: command.clone(),
+                expected: "un prénom et un nom",
+            })?;
+            let last_name = tokens.next().ok_or_else(|| CommandError::MissingArgument {
+                command: command.clone(),
+                expected: "un prénom et un nom",
+            })?;
+            Ok(Action::Find { first_name, last_name })
+        }
+        "copy" => {
+            let field = tokens.next().ok_or_else(|| CommandError::MissingArgument {
+                command: command.clone(),
+                expected: "un nom de champ (ex. email)",
+            })?;
+            Ok(Action::Copy { field })
+        }
+        "goto" => {
+            let target = tokens.next().ok_or_else(|| CommandError::MissingArgument {
+                command: command.clone(),
+                expected: "une vue (ex. settings)",
+            })?;
+            Ok(Action::Goto { target })
+        }
+        "set" => {
+            let setting = tokens.next().ok_or_else(|| CommandError::MissingArgument {
+                command: command.clone(),
+                expected: "un réglage et une valeur",
+            })?;
+            let value = tokens.next().ok_or_else(|| CommandError::MissingArgument {
+                command: command.clone(),
+                expected: "un réglage et une valeur",
+            })?;
+            match setting.as_str() {
+                "client_id" => Ok(Action::SetClientId(value)),
+                "client_secret" => Ok(Action::SetClientSecret(value)),
+                other => Err(CommandError::Unknown(format!("set {}", other))),
+            }
+        }
+        "export" => match tokens.next().as_deref() {
+            Some("csv") => Ok(Action::ExportCsv),
+            Some(other) => Err(CommandError::Unknown(format!("export {}", other))),
+            None => Err(CommandError::MissingArgument {
+                command,
+                expected: "un format (ex. csv)",
+            }),
+        },
+        "quit" => Ok(Action::Quit),
+        other => Err(CommandError::Unknown(other.to_string())),
+    }
+}
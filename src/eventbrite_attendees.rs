@@ -1,6 +1,141 @@
-use log::{debug, error};
+use log::{debug, error, warn};
 use reqwest::blocking::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use thiserror::Error;
+
+/// Distinguishes the ways an Eventbrite call can fail so callers (and the
+/// UI) can react instead of treating every failure as "no data".
+#[derive(Debug, Error)]
+pub enum EventbriteError {
+    #[error("Eventbrite rejected the access token")]
+    Auth,
+    #[error("Rate limited by Eventbrite, retry after {retry_after}s")]
+    RateLimited { retry_after: u64 },
+    #[error("Eventbrite returned HTTP {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("Failed to decode Eventbrite response: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("Network error while calling Eventbrite: {0}")]
+    Network(#[from] reqwest::Error),
+}
+
+pub(crate) fn classify_error(status: reqwest::StatusCode, retry_after: Option<u64>, body: String) -> EventbriteError {
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        EventbriteError::Auth
+    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        EventbriteError::RateLimited {
+            retry_after: retry_after.unwrap_or(60),
+        }
+    } else {
+        EventbriteError::Http {
+            status: status.as_u16(),
+            body,
+        }
+    }
+}
+
+pub(crate) fn retry_after_seconds(res: &reqwest::blocking::Response) -> Option<u64> {
+    res.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok())
+}
+
+const ATTENDEES_FETCH_CONCURRENCY_ENV_VAR: &str = "LES_PTITS_GILETS_ATTENDEES_CONCURRENCY";
+const DEFAULT_ATTENDEES_FETCH_CONCURRENCY: usize = 4;
+const ATTENDEES_FETCH_MAX_RETRIES: u32 = 3;
+
+fn attendees_fetch_concurrency() -> usize {
+    std::env::var(ATTENDEES_FETCH_CONCURRENCY_ENV_VAR)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_ATTENDEES_FETCH_CONCURRENCY)
+}
+
+/// GETs `url`, replaying the validators cached under `cache_key` as
+/// conditional-request headers and serving the cached body on a `304`
+/// instead of re-parsing an identical response.
+fn cached_get(
+    client: &Client,
+    token: &str,
+    url: &str,
+    query: &[(&str, &str)],
+    cache_key: &str,
+) -> Result<String, EventbriteError> {
+    let cached = crate::response_cache::get(cache_key);
+
+    let mut request = client.get(url).bearer_auth(token);
+    if !query.is_empty() {
+        request = request.query(query);
+    }
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag.as_str());
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified.as_str());
+        }
+    }
+
+    let start = Instant::now();
+    let res = request.send()?;
+    let status = res.status();
+    let retry_after = retry_after_seconds(&res);
+    let etag = res
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let last_modified = res
+        .headers()
+        .get(reqwest::header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+
+    if status == reqwest::StatusCode::NOT_MODIFIED {
+        crate::inspector::record_request(
+            "GET",
+            url,
+            crate::inspector::BEARER_HEADERS,
+            Some(status.as_u16()),
+            start.elapsed(),
+            "304 Not Modified — served from cache",
+        );
+        return cached.map(|c| c.body).ok_or_else(|| EventbriteError::Http {
+            status: 304,
+            body: "Received 304 Not Modified but no cached body to serve".to_string(),
+        });
+    }
+
+    let body = res.text()?;
+    crate::inspector::record_request(
+        "GET",
+        url,
+        crate::inspector::BEARER_HEADERS,
+        Some(status.as_u16()),
+        start.elapsed(),
+        &body,
+    );
+
+    if !status.is_success() {
+        return Err(classify_error(status, retry_after, body));
+    }
+
+    if etag.is_some() || last_modified.is_some() {
+        crate::response_cache::put(
+            cache_key,
+            crate::response_cache::CachedResponse {
+                etag,
+                last_modified,
+                body: body.clone(),
+            },
+        );
+    }
+
+    Ok(body)
+}
 
 #[derive(Debug, Deserialize)]
 struct Organization {
@@ -43,18 +178,29 @@ struct AttendeeResponse {
 #[derive(Debug, Deserialize)]
 struct Pagination {
     has_more_items: bool,
+    #[serde(default)]
+    page_count: Option<u32>,
+    /// Opaque token for the next page, used when Eventbrite doesn't report
+    /// `page_count` up front and pages must be walked one at a time.
+    #[serde(default)]
+    continuation: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Attendee {
+    pub id: String,
     pub profile: AttendeeProfile,
     pub created: String,
     pub ticket_class_name: Option<String>,
     pub birthdate: Option<String>,
+    /// Door check-in state. Not part of the Eventbrite payload; defaulted
+    /// on deserialize and reconciled back to Eventbrite by `crate::checkin`.
+    #[serde(default)]
+    pub checked_in: bool,
     answers: Option<Vec<Answer>>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AttendeeProfile {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
@@ -62,28 +208,66 @@ pub struct AttendeeProfile {
     pub cell_phone: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 struct Answer {
     question: String,
     #[serde(default)]
     answer: Option<String>,
 }
 
-pub fn get_attendees_from_api(token: &str) -> Result<(Vec<Attendee>, String), anyhow::Error> {
+/// Fetches the next event's attendees from the API, falling back to the
+/// last successfully fetched list (see `crate::cache`) when the API is
+/// unreachable or rate-limited — venue WiFi is frequently flaky at check-in
+/// time. The returned `Option<u64>` is the cache's `fetched_at` timestamp
+/// when the result came from the fallback, or `None` for a live fetch.
+pub fn get_attendees_from_api(
+    token: &str,
+    on_progress: impl FnMut(usize, usize),
+) -> Result<(Vec<Attendee>, String, String, Option<u64>), EventbriteError> {
+    match fetch_attendees_live(token, on_progress) {
+        Ok((attendees, event_date, event_id, event_name)) => {
+            if let Err(err) = crate::cache::save_cache(&event_id, &event_name, &event_date, &attendees) {
+                warn!("Failed to persist attendee cache: {}", err);
+            }
+            Ok((attendees, event_date, event_id, None))
+        }
+        Err(err @ (EventbriteError::Network(_) | EventbriteError::RateLimited { .. })) => {
+            match crate::cache::load_cache() {
+                Some(cache) => {
+                    warn!("Serving cached attendees from {} after error: {}", cache.fetched_at, err);
+                    Ok((cache.attendees, cache.event_date, cache.event_id, Some(cache.fetched_at)))
+                }
+                None => Err(err),
+            }
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn fetch_attendees_live(
+    token: &str,
+    on_progress: impl FnMut(usize, usize),
+) -> Result<(Vec<Attendee>, String, String, String), EventbriteError> {
     let client = Client::new();
 
     debug!("Starting to fetch organization ID...");
-    let org_id = get_organization_id(&client, token)
-        .ok_or_else(|| anyhow::anyhow!("Failed to fetch organization ID"))?;
+    let org_id = get_organization_id(&client, token)?
+        .ok_or_else(|| EventbriteError::Http {
+            status: 0,
+            body: "No organization found for this account".to_string(),
+        })?;
     debug!("Fetched organization ID: {}", org_id);
 
     debug!("Starting to fetch the next event...");
-    let event = get_next_event(&client, token, &org_id)
-        .ok_or_else(|| anyhow::anyhow!("Failed to fetch next event"))?;
+    let event = get_next_event(&client, token, &org_id)?
+        .ok_or_else(|| EventbriteError::Http {
+            status: 0,
+            body: "No upcoming event found for this organization".to_string(),
+        })?;
     debug!("Fetched next event: {} ({})", event.name.text, event.id);
 
     debug!("Starting to fetch attendees for event ID: {}", event.id);
-    let mut attendees = get_attendees(&client, token, &event.id);
+    let mut attendees = get_attendees(&client, token, &event.id, on_progress)?;
     debug!("Fetched {} attendees for event ID: {}", attendees.len(), event.id);
 
     // Sort attendees
@@ -108,124 +292,260 @@ pub fn get_attendees_from_api(token: &str) -> Result<(Vec<Attendee>, String), an
         .map(|dt| dt.format("%d/%m/%Y").to_string())
         .unwrap_or_else(|_| "<invalid date>".to_string());
 
-    Ok((attendees, event_date))
+    Ok((attendees, event_date, event.id, event.name.text))
 }
 
-pub fn get_organization_id(client: &Client, token: &str) -> Option<String> {
+pub fn get_organization_id(client: &Client, token: &str) -> Result<Option<String>, EventbriteError> {
     debug!("Fetching organization ID...");
-    let res = client
-        .get("https://www.eventbriteapi.com/v3/users/me/organizations/")
-        .bearer_auth(token)
-        .send()
-        .ok()?;
-
-    if !res.status().is_success() {
-        error!("Failed to fetch organization ID: {:?}", res.text().ok()?);
-        return None;
-    }
+    let url = "https://www.eventbriteapi.com/v3/users/me/organizations/";
+    let body = cached_get(client, token, url, &[], url)?;
 
-    let data: OrganizationsResponse = res.json().ok()?;
+    let data: OrganizationsResponse = serde_json::from_str(&body)?;
     let org_id = data.organizations.first().map(|org| org.id.clone());
     debug!("Organization ID fetched: {:?}", org_id);
-    org_id
+    Ok(org_id)
 }
 
-pub fn get_next_event(client: &Client, token: &str, org_id: &str) -> Option<Event> {
+pub fn get_next_event(
+    client: &Client,
+    token: &str,
+    org_id: &str,
+) -> Result<Option<Event>, EventbriteError> {
     debug!("Fetching next event for organization ID: {}", org_id);
-    let res = client
-        .get(&format!(
-            "https://www.eventbriteapi.com/v3/organizations/{}/events/",
-            org_id
-        ))
-        .bearer_auth(token)
-        .query(&[("order_by", "start_asc"), ("status", "live")])
-        .send()
-        .ok()?;
-
-    if !res.status().is_success() {
-        error!("Failed to fetch events: {:?}", res.text().ok()?);
-        return None;
-    }
-
-    let data: EventsResponse = res.json().ok()?;
+    let url = format!(
+        "https://www.eventbriteapi.com/v3/organizations/{}/events/",
+        org_id
+    );
+    let body = cached_get(
+        client,
+        token,
+        &url,
+        &[("order_by", "start_asc"), ("status", "live")],
+        &url,
+    )?;
+
+    let data: EventsResponse = serde_json::from_str(&body)?;
     let next_event = data.events.into_iter().next();
     debug!("Next event fetched: {:?}", next_event);
-    next_event
+    Ok(next_event)
 }
 
-pub fn get_attendees(client: &Client, token: &str, event_id: &str) -> Vec<Attendee> {
-    debug!("Fetching attendees for event ID: {}", event_id);
-    let mut attendees = vec![];
-    let mut page = 1;
+/// Extracts the "date de naissance" custom question, if any, into the
+/// dedicated `birthdate` field so callers don't have to dig through
+/// `answers` themselves.
+fn extract_birthdates(attendees: &mut [Attendee]) {
+    for attendee in attendees {
+        if let Some(answers) = &attendee.answers {
+            attendee.birthdate = answers
+                .iter()
+                .find(|answer| answer.question.to_lowercase() == "date de naissance")
+                .and_then(|answer| answer.answer.clone());
+        }
+    }
+}
 
-    loop {
-        debug!("Fetching attendees, page: {}", page);
-        let res = client
-            .get(&format!(
-                "https://www.eventbriteapi.com/v3/events/{}/attendees/",
-                event_id
-            ))
-            .bearer_auth(token)
-            .query(&[("page", page.to_string())])
-            .send();
-
-        match res {
-            Ok(response) => {
-                if !response.status().is_success() {
-                    error!(
-                        "Failed to fetch attendees for page: {}. Status: {}. Body: {:?}",
-                        page,
-                        response.status(),
-                        response.text().ok()
-                    );
-                    break;
-                }
+/// Fetches one page of attendees by page number. Shared by the first-page
+/// probe and the concurrent fetch of the remaining pages below, used when
+/// the first page reports a `page_count` up front.
+fn fetch_attendees_page(
+    client: &Client,
+    token: &str,
+    event_id: &str,
+    page: u32,
+) -> Result<(Vec<Attendee>, Pagination), EventbriteError> {
+    debug!("Fetching attendees, page: {}", page);
+    let url = format!(
+        "https://www.eventbriteapi.com/v3/events/{}/attendees/",
+        event_id
+    );
+    // Cached per (event_id, page) so only pages that actually changed since
+    // the last refresh are re-parsed.
+    let cache_key = format!("{}:{}", event_id, page);
+    let page_str = page.to_string();
+    let raw_body = cached_get(client, token, &url, &[("page", &page_str)], &cache_key)?;
+
+    debug!("Raw response body for page {}: {}", page, raw_body);
+
+    let mut data: AttendeeResponse = serde_json::from_str(&raw_body).map_err(|err| {
+        error!(
+            "Failed to parse attendees response for page: {}. Error: {}. Body: {}",
+            page, err, raw_body
+        );
+        err
+    })?;
+
+    extract_birthdates(&mut data.attendees);
+
+    debug!(
+        "Fetched {} attendees from page: {}",
+        data.attendees.len(),
+        page
+    );
+    Ok((data.attendees, data.pagination))
+}
 
-                let raw_body = response.text().unwrap_or_else(|_| "Failed to read body".to_string());
-                debug!("Raw response body for page {}: {}", page, raw_body);
+/// Fetches one page of attendees by following Eventbrite's `continuation`
+/// token rather than a page number, for the (less common) case where the
+/// first page didn't report a `page_count` up front. `continuation` is
+/// `None` only for the very first call.
+fn fetch_attendees_continuation(
+    client: &Client,
+    token: &str,
+    event_id: &str,
+    continuation: Option<&str>,
+) -> Result<(Vec<Attendee>, Pagination), EventbriteError> {
+    debug!("Fetching attendees, continuation: {:?}", continuation);
+    let url = format!(
+        "https://www.eventbriteapi.com/v3/events/{}/attendees/",
+        event_id
+    );
+    let cache_key = match continuation {
+        Some(cont) => format!("{}:continuation:{}", event_id, cont),
+        None => format!("{}:continuation:first", event_id),
+    };
+    let mut query: Vec<(&str, &str)> = Vec::new();
+    if let Some(cont) = continuation {
+        query.push(("continuation", cont));
+    }
+    let raw_body = cached_get(client, token, &url, &query, &cache_key)?;
+
+    let mut data: AttendeeResponse = serde_json::from_str(&raw_body).map_err(|err| {
+        error!(
+            "Failed to parse attendees response for continuation {:?}. Error: {}. Body: {}",
+            continuation, err, raw_body
+        );
+        err
+    })?;
+
+    extract_birthdates(&mut data.attendees);
+
+    debug!(
+        "Fetched {} attendees for continuation: {:?}",
+        data.attendees.len(),
+        continuation
+    );
+    Ok((data.attendees, data.pagination))
+}
 
-                let mut data = match serde_json::from_str::<AttendeeResponse>(&raw_body) {
-                    Ok(parsed) => parsed,
-                    Err(err) => {
-                        error!(
-                            "Failed to parse attendees response for page: {}. Error: {}. Body: {}",
-                            page, err, raw_body
-                        );
-                        break;
-                    }
-                };
-
-                // Extract birthdate from answers
-                for attendee in &mut data.attendees {
-                    if let Some(answers) = &attendee.answers {
-                        attendee.birthdate = answers
-                            .iter()
-                            .find(|answer| answer.question.to_lowercase() == "date de naissance")
-                            .and_then(|answer| answer.answer.clone());
-                    }
-                }
+/// `fetch_attendees_page`, honoring `EventbriteError::RateLimited` by
+/// sleeping for `retry_after` and retrying, up to `ATTENDEES_FETCH_MAX_RETRIES`
+/// times, so a worker in the concurrent pool below backs off instead of
+/// tripping Eventbrite's throttling further.
+fn fetch_attendees_page_with_backoff(
+    client: &Client,
+    token: &str,
+    event_id: &str,
+    page: u32,
+) -> Result<(Vec<Attendee>, Pagination), EventbriteError> {
+    let mut attempt = 0;
+    loop {
+        match fetch_attendees_page(client, token, event_id, page) {
+            Err(EventbriteError::RateLimited { retry_after }) if attempt < ATTENDEES_FETCH_MAX_RETRIES => {
+                warn!(
+                    "Rate limited fetching attendees page {}, retrying in {}s (attempt {}/{})",
+                    page, retry_after, attempt + 1, ATTENDEES_FETCH_MAX_RETRIES
+                );
+                std::thread::sleep(std::time::Duration::from_secs(retry_after));
+                attempt += 1;
+            }
+            other => return other,
+        }
+    }
+}
+
+pub fn get_attendees(
+    client: &Client,
+    token: &str,
+    event_id: &str,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<Vec<Attendee>, EventbriteError> {
+    debug!("Fetching attendees for event ID: {}", event_id);
 
+    // The first page reveals the total page count, so the rest can be
+    // dispatched in parallel instead of waiting on one round trip at a time.
+    let (mut attendees, first_page) = fetch_attendees_page(client, token, event_id, 1)?;
+    let total_pages = first_page.page_count.map(|n| n as usize).unwrap_or(0);
+    let mut pages_loaded = 1usize;
+    on_progress(pages_loaded, total_pages);
+
+    if first_page.has_more_items {
+        match first_page.page_count {
+            Some(total_pages) if total_pages > 1 => {
+                let concurrency = attendees_fetch_concurrency();
                 debug!(
-                    "Fetched {} attendees from page: {}",
-                    data.attendees.len(),
-                    page
+                    "First page reported {} total pages; fetching the rest with up to {} workers in parallel",
+                    total_pages, concurrency
                 );
-                attendees.extend(data.attendees);
 
-                if !data.pagination.has_more_items {
-                    debug!("No more pages of attendees to fetch.");
-                    break;
+                let remaining_pages: Vec<u32> = (2..=total_pages).collect();
+                for batch in remaining_pages.chunks(concurrency) {
+                    let mut batch_results: Vec<(u32, Result<(Vec<Attendee>, Pagination), EventbriteError>)> =
+                        std::thread::scope(|scope| {
+                            let handles: Vec<_> = batch
+                                .iter()
+                                .map(|&page| {
+                                    scope.spawn(move || {
+                                        (page, fetch_attendees_page_with_backoff(client, token, event_id, page))
+                                    })
+                                })
+                                .collect();
+                            handles
+                                .into_iter()
+                                .map(|handle| handle.join().expect("attendee fetch worker panicked"))
+                                .collect()
+                        });
+
+                    // Threads in a batch can finish out of order; restore
+                    // page order before extending so the existing sort in
+                    // `fetch_attendees_live` still runs against a deterministic input.
+                    batch_results.sort_by_key(|(page, _)| *page);
+                    for (_, result) in batch_results {
+                        let (page_attendees, _) = result?;
+                        attendees.extend(page_attendees);
+                        pages_loaded += 1;
+                    }
+                    on_progress(pages_loaded, total_pages as usize);
                 }
-
-                page += 1;
-            }
-            Err(err) => {
-                error!("Failed to fetch attendees for page: {}. Error: {}", page, err);
-                break;
             }
+            // Eventbrite doesn't always report a page count up front; when
+            // it doesn't, follow the `continuation` token from each
+            // response one at a time instead of guessing how many pages to
+            // fetch in parallel.
+            _ => match first_page.continuation {
+                Some(mut continuation) => loop {
+                    let (page_attendees, pagination) =
+                        fetch_attendees_continuation(client, token, event_id, Some(&continuation))?;
+                    attendees.extend(page_attendees);
+                    pages_loaded += 1;
+                    on_progress(pages_loaded, 0);
+                    if !pagination.has_more_items {
+                        break;
+                    }
+                    match pagination.continuation {
+                        Some(next) => continuation = next,
+                        None => break,
+                    }
+                },
+                // Neither a page count nor a continuation token: fall back
+                // to walking page numbers one at a time.
+                None => {
+                    let mut page = 2;
+                    loop {
+                        let (page_attendees, pagination) =
+                            fetch_attendees_page_with_backoff(client, token, event_id, page)?;
+                        attendees.extend(page_attendees);
+                        pages_loaded += 1;
+                        on_progress(pages_loaded, 0);
+                        if !pagination.has_more_items {
+                            break;
+                        }
+                        page += 1;
+                    }
+                }
+            },
         }
     }
 
     debug!("Total attendees fetched: {}", attendees.len());
-    attendees
+    Ok(attendees)
 }
@@ -5,28 +5,231 @@ use std::{
     path::PathBuf,
     sync::{Arc, Mutex},
     thread,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
 };
 use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use rand::RngCore;
+use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use webbrowser;
 use log::{debug, info, error};
 use dirs;
 
 const CONFIG_FILE: &str = ".les_ptits_gilets_config.json";
-const REDIRECT_URI: &str = "http://localhost:5000/callback";
 const TOKEN_URL: &str = "https://www.eventbrite.com/oauth/token";
+const ENCRYPT_CONFIG_ENV_VAR: &str = "LES_PTITS_GILETS_ENCRYPT";
+const CONFIG_ENVELOPE_VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const DEFAULT_CALLBACK_PORT: u16 = 5000;
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Config {
+    pub accounts: AccountsManager,
+    #[serde(default)]
+    pub theme_name: crate::theme::ThemeName,
+}
+
+/// A single Eventbrite account/organization a volunteer has authorized.
+/// Each account keeps its own credentials and cached token so switching
+/// accounts never requires re-entering a client id/secret.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Account {
+    pub name: String,
     pub client_id: Option<String>,
-    pub client_secret: Option<String>,
+    pub client_secret: Option<SecretString>,
     token_info: Option<TokenInfo>,
+    #[serde(default = "default_callback_port")]
+    pub callback_port: u16,
+}
+
+fn default_callback_port() -> u16 {
+    DEFAULT_CALLBACK_PORT
+}
+
+impl Account {
+    fn new(name: String, callback_port: u16) -> Self {
+        Self {
+            name,
+            client_id: None,
+            client_secret: None,
+            token_info: None,
+            callback_port,
+        }
+    }
+
+    /// Timestamp the cached access token was last issued/refreshed at, used
+    /// by callers to tell whether a token fetch actually changed anything
+    /// worth persisting (rather than just serving the existing cached token).
+    pub fn token_created_at(&self) -> Option<u64> {
+        self.token_info.as_ref().map(|t| t.created_at)
+    }
+}
+
+/// Owns the list of configured accounts and tracks which one is active.
+/// Serialized as a single JSON document alongside the rest of the config.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct AccountsManager {
+    pub accounts: Vec<Account>,
+    pub active: usize,
+}
+
+impl AccountsManager {
+    pub fn active_account(&self) -> Option<&Account> {
+        self.accounts.get(self.active)
+    }
+
+    pub fn active_account_mut(&mut self) -> Option<&mut Account> {
+        self.accounts.get_mut(self.active)
+    }
+
+    /// Adds a new account, wired to its own OAuth callback port so its
+    /// authorization round-trip can't collide with another account's.
+    pub fn add_account(&mut self, name: String) -> usize {
+        let port = self
+            .accounts
+            .iter()
+            .map(|account| account.callback_port + 1)
+            .max()
+            .unwrap_or(DEFAULT_CALLBACK_PORT);
+        self.accounts.push(Account::new(name, port));
+        self.active = self.accounts.len() - 1;
+        self.active
+    }
+
+    pub fn remove_account(&mut self, index: usize) {
+        if index >= self.accounts.len() {
+            return;
+        }
+        self.accounts.remove(index);
+        if self.active >= self.accounts.len() {
+            self.active = self.accounts.len().saturating_sub(1);
+        }
+    }
+}
+
+/// Shape of the config file before multi-account support; used only to
+/// migrate old on-disk configs into a single-entry `AccountsManager`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct LegacyConfig {
+    client_id: Option<String>,
+    client_secret: Option<SecretString>,
+    token_info: Option<TokenInfo>,
+}
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy: LegacyConfig) -> Self {
+        Config {
+            accounts: AccountsManager {
+                accounts: vec![Account {
+                    name: "Compte par défaut".to_string(),
+                    client_id: legacy.client_id,
+                    client_secret: legacy.client_secret,
+                    token_info: legacy.token_info,
+                    callback_port: DEFAULT_CALLBACK_PORT,
+                }],
+                active: 0,
+            },
+            theme_name: crate::theme::ThemeName::default(),
+        }
+    }
+}
+
+/// On-disk envelope for an encrypted config: everything needed to re-derive
+/// the key and decrypt, base64-encoded so the whole thing round-trips as JSON.
+#[derive(Serialize, Deserialize, Debug)]
+struct ConfigEnvelope {
+    version: u8,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn encryption_enabled() -> bool {
+    std::env::var(ENCRYPT_CONFIG_ENV_VAR)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive encryption key: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt_config(config: &Config, passphrase: &str) -> Result<ConfigEnvelope> {
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| anyhow!("Invalid key: {}", e))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let plaintext = serde_json::to_vec(config)?;
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext.as_ref())
+        .map_err(|e| anyhow!("Failed to encrypt config: {}", e))?;
+
+    Ok(ConfigEnvelope {
+        version: CONFIG_ENVELOPE_VERSION,
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce_bytes),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn decrypt_config(envelope: &ConfigEnvelope, passphrase: &str) -> Result<Config> {
+    if envelope.version != CONFIG_ENVELOPE_VERSION {
+        return Err(anyhow!(
+            "Unsupported config envelope version: {}",
+            envelope.version
+        ));
+    }
+
+    let salt = BASE64
+        .decode(&envelope.salt)
+        .map_err(|e| anyhow!("Corrupt config envelope (salt): {}", e))?;
+    let nonce_bytes = BASE64
+        .decode(&envelope.nonce)
+        .map_err(|e| anyhow!("Corrupt config envelope (nonce): {}", e))?;
+    let ciphertext = BASE64
+        .decode(&envelope.ciphertext)
+        .map_err(|e| anyhow!("Corrupt config envelope (ciphertext): {}", e))?;
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| anyhow!("Invalid key: {}", e))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let plaintext = cipher.decrypt(nonce, ciphertext.as_ref()).map_err(|_| {
+        anyhow!("Wrong passphrase or tampered config: failed to authenticate config contents")
+    })?;
+
+    let config: Config = serde_json::from_slice(&plaintext)?;
+    Ok(config)
+}
+
+fn prompt_passphrase(prompt: &str) -> Result<String> {
+    rpassword::prompt_password(prompt).map_err(|e| anyhow!("Failed to read passphrase: {}", e))
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct TokenInfo {
-    access_token: String,
+    access_token: SecretString,
+    /// Long-lived token used to obtain a new access token without sending
+    /// the volunteer back through the browser. Eventbrite doesn't always
+    /// issue one, so refresh falls back to a full authorization when absent.
+    #[serde(default)]
+    refresh_token: Option<SecretString>,
     #[serde(default)]
     created_at: u64,
 }
@@ -39,58 +242,112 @@ fn get_config_path() -> PathBuf {
 
 pub fn load_config() -> Result<Config> {
     let path = get_config_path();
-    if path.exists() {
-        let data = fs::read_to_string(path)?;
-        let config: Config = serde_json::from_str(&data)?;
-        Ok(config)
-    } else {
-        Ok(Config::default())
+    if !path.exists() {
+        return Ok(Config::default());
     }
+
+    let data = fs::read_to_string(path)?;
+
+    // An encrypted config is a small envelope JSON; a legacy plaintext config
+    // is the `Config` struct directly. Try the envelope first so existing,
+    // unencrypted configs keep loading exactly as before.
+    if let Ok(envelope) = serde_json::from_str::<ConfigEnvelope>(&data) {
+        let passphrase = prompt_passphrase("Passphrase pour déchiffrer la configuration: ")?;
+        return decrypt_config(&envelope, &passphrase);
+    }
+
+    if let Ok(config) = serde_json::from_str::<Config>(&data) {
+        return Ok(config);
+    }
+
+    // Fall back to the pre-AccountsManager single-account shape.
+    let legacy: LegacyConfig = serde_json::from_str(&data)?;
+    Ok(legacy.into())
 }
 
 pub fn save_config(config: &Config) -> Result<()> {
     let path = get_config_path();
-    let data = serde_json::to_string_pretty(config)?;
-    fs::write(path, data)?;
+
+    if encryption_enabled() {
+        let passphrase =
+            prompt_passphrase("Choisissez une passphrase pour chiffrer la configuration: ")?;
+        let envelope = encrypt_config(config, &passphrase)?;
+        let data = serde_json::to_string_pretty(&envelope)?;
+        fs::write(path, data)?;
+    } else {
+        let data = serde_json::to_string_pretty(config)?;
+        fs::write(path, data)?;
+    }
+
     Ok(())
 }
 
-pub fn get_access_token() -> Result<String> {
-    debug!("Fetching access token");
-    let mut config = load_config()?;
+/// Fetches (and caches, on `account`) an access token for the given account.
+/// When `force_refresh` is set, the cached access token is treated as stale
+/// even if it's within its usual window (used after a call comes back with
+/// a 401). Purely in-memory plus network I/O — never touches the on-disk
+/// config — so it's safe to call from a background thread with a cloned
+/// `Account`. Callers are responsible for persisting the config afterwards
+/// (on the main thread — see `app.rs`'s `App::apply_refreshed_account`) so
+/// the refreshed `token_info` survives restarts.
+pub fn get_access_token(account: &mut Account, force_refresh: bool) -> Result<String> {
+    debug!("Fetching access token for account '{}'", account.name);
 
-    if let Some(token_info) = &config.token_info {
-        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-        if now - token_info.created_at < 3600 {
-            debug!("Using cached token");
-            return Ok(token_info.access_token.clone());
+    if !force_refresh {
+        if let Some(token_info) = &account.token_info {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            if now - token_info.created_at < 3600 {
+                debug!("Using cached token");
+                return Ok(token_info.access_token.expose_secret().clone());
+            }
         }
     }
 
-    let client_id = config.client_id.clone().ok_or_else(|| {
-        error!("CLIENT_ID not set");
+    let client_id = account.client_id.clone().ok_or_else(|| {
+        error!("CLIENT_ID not set for account '{}'", account.name);
         anyhow!("CLIENT_ID not set")
     })?;
-    let client_secret = config.client_secret.clone().ok_or_else(|| {
-        error!("CLIENT_SECRET not set");
+    let client_secret = account.client_secret.clone().ok_or_else(|| {
+        error!("CLIENT_SECRET not set for account '{}'", account.name);
         anyhow!("CLIENT_SECRET not set")
     })?;
 
-    let code = request_user_authorization(&client_id)?;
-    let token = exchange_code_for_token(&client_id, &client_secret, &code)?;
+    // Prefer a silent refresh over sending the volunteer back through the
+    // browser, falling back to a full authorization if there's no refresh
+    // token yet or Eventbrite has since revoked it.
+    if let Some(refresh_token) = account.token_info.as_ref().and_then(|t| t.refresh_token.clone()) {
+        match refresh_access_token(&client_id, &client_secret, &refresh_token, account.callback_port) {
+            Ok(token) => {
+                let access_token = token.access_token.expose_secret().clone();
+                account.token_info = Some(token);
+                debug!("Access token refreshed successfully");
+                return Ok(access_token);
+            }
+            Err(err) => {
+                debug!("Refresh token rejected, falling back to full authorization: {}", err);
+            }
+        }
+    }
+
+    let code = request_user_authorization(&client_id, account.callback_port)?;
+    let token = exchange_code_for_token(&client_id, &client_secret, &code, account.callback_port)?;
 
-    config.token_info = Some(token.clone());
-    save_config(&config)?;
+    let access_token = token.access_token.expose_secret().clone();
+    account.token_info = Some(token);
 
     debug!("Access token fetched successfully");
-    Ok(token.access_token)
+    Ok(access_token)
+}
+
+fn redirect_uri(callback_port: u16) -> String {
+    format!("http://localhost:{}/callback", callback_port)
 }
 
-fn request_user_authorization(client_id: &str) -> Result<String> {
+fn request_user_authorization(client_id: &str, callback_port: u16) -> Result<SecretString> {
     debug!("Requesting user authorization...");
     let auth_url = format!(
         "https://www.eventbrite.com/oauth/authorize?response_type=code&client_id={}&redirect_uri={}",
-        client_id, REDIRECT_URI
+        client_id, redirect_uri(callback_port)
     );
 
     info!("ðŸŒ Opening browser for authorization...");
@@ -103,8 +360,8 @@ fn request_user_authorization(client_id: &str) -> Result<String> {
     let auth_code_clone = Arc::clone(&auth_code);
 
     let server_thread = thread::spawn(move || {
-        if let Ok(listener) = TcpListener::bind("127.0.0.1:5000") {
-            debug!("Listening for incoming HTTP requests on port 5000...");
+        if let Ok(listener) = TcpListener::bind(format!("127.0.0.1:{}", callback_port)) {
+            debug!("Listening for incoming HTTP requests on port {}...", callback_port);
             for stream in listener.incoming() {
                 if let Ok(mut stream) = stream {
                     let mut buffer = [0; 1024];
@@ -121,10 +378,10 @@ fn request_user_authorization(client_id: &str) -> Result<String> {
                                     .unwrap_or("")
                                     .to_string();
 
-                                debug!("Authorization code received: {}", code);
+                                debug!("Authorization code received");
                                 let response = b"HTTP/1.1 200 OK\r\n\r\nAuthorization successful. You may close this window.";
                                 stream.write_all(response).unwrap();
-                                *auth_code_clone.lock().unwrap() = Some(code);
+                                *auth_code_clone.lock().unwrap() = Some(SecretString::new(code));
                                 break;
                             }
                         }
@@ -141,36 +398,93 @@ fn request_user_authorization(client_id: &str) -> Result<String> {
     code_guard.clone().ok_or(anyhow!("No authorization code received."))
 }
 
-fn exchange_code_for_token(client_id: &str, client_secret: &str, code: &str) -> Result<TokenInfo> {
+fn exchange_code_for_token(
+    client_id: &str,
+    client_secret: &SecretString,
+    code: &SecretString,
+    callback_port: u16,
+) -> Result<TokenInfo> {
     debug!("Exchanging authorization code for access token...");
     let client = reqwest::blocking::Client::new();
+    let redirect_uri = redirect_uri(callback_port);
 
     let params = [
         ("grant_type", "authorization_code"),
         ("client_id", client_id),
-        ("client_secret", client_secret),
-        ("code", code),
-        ("redirect_uri", REDIRECT_URI),
+        ("client_secret", client_secret.expose_secret()),
+        ("code", code.expose_secret()),
+        ("redirect_uri", &redirect_uri),
     ];
 
+    let start = Instant::now();
     let resp = client.post(TOKEN_URL).form(&params).send()?;
+    let status = resp.status();
+    let resp_text = resp.text()?;
+    crate::inspector::record_request(
+        "POST",
+        TOKEN_URL,
+        "client_secret: [REDACTED], code: [REDACTED]",
+        Some(status.as_u16()),
+        start.elapsed(),
+        &resp_text,
+    );
 
-    // Check if the response is successful
-    if !resp.status().is_success() {
-        let resp_text = resp.text()?;  // Read the response text only after status check
+    if !status.is_success() {
         error!("Failed to exchange token: {}", resp_text);
         return Err(anyhow!("Failed to exchange token: {}", resp_text));
     }
 
-    // Now that we've checked the status, read the response text
-    let resp_text = resp.text()?;
-    
     // Deserialize the response into TokenInfo
     let mut token: TokenInfo = serde_json::from_str(&resp_text)?;
     token.created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-    
+
     info!("ðŸ”“ Token obtained and cached.");
     debug!("Token received: {:?}", token);
-    
+
+    Ok(token)
+}
+
+fn refresh_access_token(
+    client_id: &str,
+    client_secret: &SecretString,
+    refresh_token: &SecretString,
+    callback_port: u16,
+) -> Result<TokenInfo> {
+    debug!("Refreshing access token...");
+    let client = reqwest::blocking::Client::new();
+    let redirect_uri = redirect_uri(callback_port);
+
+    let params = [
+        ("grant_type", "refresh_token"),
+        ("client_id", client_id),
+        ("client_secret", client_secret.expose_secret()),
+        ("refresh_token", refresh_token.expose_secret()),
+        ("redirect_uri", &redirect_uri),
+    ];
+
+    let start = Instant::now();
+    let resp = client.post(TOKEN_URL).form(&params).send()?;
+    let status = resp.status();
+    let resp_text = resp.text()?;
+    crate::inspector::record_request(
+        "POST",
+        TOKEN_URL,
+        "client_secret: [REDACTED], refresh_token: [REDACTED]",
+        Some(status.as_u16()),
+        start.elapsed(),
+        &resp_text,
+    );
+
+    if !status.is_success() {
+        error!("Failed to refresh token: {}", resp_text);
+        return Err(anyhow!("Failed to refresh token: {}", resp_text));
+    }
+
+    let mut token: TokenInfo = serde_json::from_str(&resp_text)?;
+    token.created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    info!("ðŸ”“ Token refreshed and cached.");
+    debug!("Token received: {:?}", token);
+
     Ok(token)
 }
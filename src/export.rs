@@ -0,0 +1,61 @@
+//! Writes the loaded attendee list to a file as CSV or JSON, picked by the
+//! destination path's extension (`.json`, CSV otherwise), for organizers who
+//! need a durable export rather than clipboard-only copies.
+
+use crate::eventbrite_attendees::Attendee;
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+
+/// Writes `attendees` to `path`, as JSON if the extension is `.json` and as
+/// CSV otherwise.
+pub fn export_attendees(attendees: &[Attendee], path: &str) -> Result<()> {
+    let is_json = Path::new(path)
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("json"))
+        .unwrap_or(false);
+
+    let file = std::fs::File::create(path)?;
+    let mut writer = std::io::BufWriter::new(file);
+
+    if is_json {
+        serde_json::to_writer_pretty(&mut writer, attendees)?;
+    } else {
+        write_csv(&mut writer, attendees)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_csv(writer: &mut impl Write, attendees: &[Attendee]) -> Result<()> {
+    writeln!(
+        writer,
+        "Prénom,Nom,Email,Téléphone,Type d'inscription,Date d'inscription"
+    )?;
+
+    for attendee in attendees {
+        let fields = [
+            attendee.profile.first_name.as_deref().unwrap_or(""),
+            attendee.profile.last_name.as_deref().unwrap_or(""),
+            attendee.profile.email.as_deref().unwrap_or(""),
+            attendee.profile.cell_phone.as_deref().unwrap_or(""),
+            attendee.ticket_class_name.as_deref().unwrap_or(""),
+            &attendee.created,
+        ];
+        let row = fields.iter().map(|field| csv_escape(field)).collect::<Vec<_>>().join(",");
+        writeln!(writer, "{}", row)?;
+    }
+
+    Ok(())
+}
+
+/// Wraps `field` in double quotes (escaping embedded quotes) when it
+/// contains a comma, a quote, or a newline, per the usual CSV quoting rule.
+fn csv_escape(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
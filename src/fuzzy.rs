@@ -0,0 +1,215 @@
+//! Accent-insensitive, typo-tolerant name matching shared by the
+//! find-by-name view and the attendee table's incremental filter.
+//!
+//! French names carry accents that volunteers typing on unfamiliar
+//! keyboards routinely drop or mistype ("Andre" vs "André", "Muller" vs
+//! "Müller"), so matching is done on a normalized form and scored rather
+//! than requiring an exact hit.
+
+/// Lowercases and strips diacritics so "André" and "Andre" compare equal.
+pub fn normalize(input: &str) -> String {
+    input
+        .chars()
+        .flat_map(|c| c.to_lowercase())
+        .filter(|c| !is_combining_mark(*c))
+        .map(strip_diacritic)
+        .collect()
+}
+
+fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036f}')
+}
+
+/// Folds the common Latin-1/Latin Extended accented letters down to their
+/// base ASCII form without pulling in a full Unicode normalization crate.
+fn strip_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        other => other,
+    }
+}
+
+/// Scores `candidate` against `query` using the edit distance between
+/// `query` and its best-matching substring of `candidate`, higher is
+/// better. Returns `None` when the two strings have too little in common
+/// to be considered a match. An empty query matches everything with a
+/// neutral score.
+///
+/// Matching against the best substring (rather than the whole candidate)
+/// is what lets a short, partial filter like `"Jo"` match `"John Doe"`:
+/// the full-string distance between those two is large simply because
+/// `"John Doe"` is much longer than the query, even though `"Jo"` is an
+/// exact prefix.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let q = normalize(query);
+    let c = normalize(candidate);
+
+    if q.is_empty() {
+        return Some(0);
+    }
+    if c.is_empty() {
+        return None;
+    }
+
+    let q_chars: Vec<char> = q.chars().collect();
+    let c_chars: Vec<char> = c.chars().collect();
+    let distance = best_substring_distance(&q_chars, &c_chars);
+
+    // Reject candidates that share almost nothing with the query: more
+    // than half of the query would have to be edited to appear in them.
+    if distance * 2 > q_chars.len() {
+        return None;
+    }
+
+    let max_len = q_chars.len().max(c_chars.len()) as i32;
+    Some(max_len - distance as i32)
+}
+
+/// Minimum edit distance between `q` and any substring of `c`, i.e. how
+/// many insert/delete/substitute operations it takes to turn some run of
+/// `c` into `q`. Standard approximate-substring-search DP: like
+/// [`levenshtein`], but the first row is all zeros so an alignment is free
+/// to start at any position in `c`, and the answer is the minimum over the
+/// last row rather than just its last cell (so an alignment is also free
+/// to end anywhere in `c`).
+fn best_substring_distance(q: &[char], c: &[char]) -> usize {
+    let mut prev = vec![0usize; c.len() + 1];
+    let mut curr = vec![0usize; c.len() + 1];
+
+    for (i, qc) in q.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cc) in c.iter().enumerate() {
+            let cost = if qc == cc { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev.into_iter().min().unwrap_or(q.len())
+}
+
+/// Greedily maps each query character, in order, onto the candidate index it
+/// matched at, for highlighting matched characters in the UI.
+pub fn matched_positions(query: &str, candidate: &str) -> Vec<usize> {
+    let q = normalize(query);
+    let c = normalize(candidate);
+
+    let mut positions = Vec::new();
+    let mut q_chars = q.chars().peekable();
+    for (idx, ch) in c.chars().enumerate() {
+        if let Some(&next) = q_chars.peek() {
+            if ch == next {
+                positions.push(idx);
+                q_chars.next();
+            }
+        } else {
+            break;
+        }
+    }
+    positions
+}
+
+/// Scores `candidate` as a fuzzy subsequence match of `query`, in the style
+/// of editor fuzzy finders: every query character (case/accent-insensitive)
+/// must appear in `candidate` in order, consecutive matches and matches at
+/// word boundaries (start of string or following a space) are rewarded, and
+/// skipping candidate characters between matches costs a small gap penalty.
+/// Unlike [`fuzzy_score`], a query character can never be skipped — a
+/// candidate that doesn't contain the full query as a subsequence scores
+/// `None`. The final score is normalized by query length so names of very
+/// different lengths remain comparable. An empty query matches everything
+/// with a neutral score.
+///
+/// Runs in O(query.len() * candidate.len()) time using a single DP row of
+/// length `candidate.len() + 1`, carried forward one query character at a
+/// time (O(candidate.len()) space).
+pub fn subsequence_score(query: &str, candidate: &str) -> Option<f64> {
+    const MATCH_SCORE: i32 = 16;
+    const CONSECUTIVE_BONUS: i32 = 8;
+    const WORD_BOUNDARY_BONUS: i32 = 12;
+    const GAP_PENALTY: i32 = 1;
+    const NEG_INF: i32 = i32::MIN / 2;
+
+    let q = normalize(query);
+    let c = normalize(candidate);
+
+    if q.is_empty() {
+        return Some(0.0);
+    }
+    if c.is_empty() {
+        return None;
+    }
+
+    let q_chars: Vec<char> = q.chars().collect();
+    let c_chars: Vec<char> = c.chars().collect();
+
+    // `row[j]` holds the best score of matching the query chars consumed so
+    // far against `c_chars[..j]`, ending with a match exactly at `j - 1`.
+    // `NEG_INF` marks a position that isn't a valid ending for a match.
+    // Before any query char is consumed there's nothing to end a match on
+    // yet, so every position is the (free) empty match: all zeros.
+    let mut row = vec![0i32; c_chars.len() + 1];
+
+    for (i, &qc) in q_chars.iter().enumerate() {
+        let mut next_row = vec![NEG_INF; c_chars.len() + 1];
+        // Best value of `row[k] - GAP_PENALTY * (j - 1 - k)` over `k <= j - 1`,
+        // maintained incrementally as `j` grows.
+        let mut best_prefix = row[0];
+
+        for j in 1..=c_chars.len() {
+            best_prefix = (best_prefix - GAP_PENALTY).max(row[j - 1]);
+
+            // Only a reachable `best_prefix` may extend into a match here —
+            // otherwise the `NEG_INF` sentinel itself would get decremented
+            // into a finite-looking (but still bogus) value via the `- GAP_PENALTY`
+            // above, letting an invalid subsequence escape as a low real score
+            // instead of `None`.
+            if best_prefix > NEG_INF && c_chars[j - 1] == qc {
+                let word_boundary = j == 1 || c_chars[j - 2] == ' ';
+                let consecutive = i > 0 && row[j - 1] != NEG_INF && best_prefix == row[j - 1];
+
+                let mut bonus = MATCH_SCORE;
+                if word_boundary {
+                    bonus += WORD_BOUNDARY_BONUS;
+                }
+                if consecutive {
+                    bonus += CONSECUTIVE_BONUS;
+                }
+
+                next_row[j] = best_prefix + bonus;
+            }
+        }
+
+        row = next_row;
+    }
+
+    let best = row.into_iter().max().unwrap_or(NEG_INF);
+    if best <= NEG_INF {
+        return None;
+    }
+
+    Some(best as f64 / q_chars.len() as f64)
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
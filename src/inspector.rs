@@ -0,0 +1,113 @@
+//! Records recent Eventbrite API calls into a bounded ring buffer so staff
+//! can see what actually happened (status, timing, response body) from
+//! inside the TUI instead of restarting with `RUST_LOG=debug`.
+
+use chrono::Local;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone)]
+pub struct InspectorEntry {
+    pub timestamp: String,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub latency: Duration,
+    pub headers: String,
+    pub response_body: String,
+}
+
+#[derive(Default)]
+pub struct Inspector {
+    entries: Mutex<VecDeque<InspectorEntry>>,
+}
+
+impl Inspector {
+    fn record(&self, entry: InspectorEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> Vec<InspectorEntry> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+pub static INSPECTOR: Lazy<Inspector> = Lazy::new(Inspector::default);
+
+/// JSON object keys whose values are secrets (access/refresh tokens, client
+/// secrets) and must never be rendered verbatim in the Inspector view, even
+/// though the surrounding response body is otherwise shown as-is for
+/// debugging.
+const REDACTED_BODY_FIELDS: &[&str] = &["access_token", "refresh_token", "client_secret"];
+
+/// Records one intercepted request/response pair. `headers` should already
+/// have any `Authorization` value redacted by the caller; `body` is redacted
+/// here so secret-bearing response fields (e.g. Eventbrite's OAuth token
+/// payloads) never reach the Inspector in cleartext.
+pub fn record_request(
+    method: &str,
+    url: &str,
+    headers: &str,
+    status: Option<u16>,
+    latency: Duration,
+    body: &str,
+) {
+    INSPECTOR.record(InspectorEntry {
+        timestamp: Local::now().format("%H:%M:%S").to_string(),
+        method: method.to_string(),
+        url: url.to_string(),
+        status,
+        latency,
+        headers: headers.to_string(),
+        response_body: pretty_json(&redact_secret_fields(body)),
+    });
+}
+
+fn pretty_json(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .and_then(|v| serde_json::to_string_pretty(&v))
+        .unwrap_or_else(|_| body.to_string())
+}
+
+/// Replaces the value of any `REDACTED_BODY_FIELDS` key, at any nesting
+/// depth, with `"[REDACTED]"`. Leaves `body` untouched if it isn't valid
+/// JSON (the caller falls back to displaying it as-is).
+fn redact_secret_fields(body: &str) -> String {
+    let Ok(mut value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.to_string();
+    };
+    redact_value(&mut value);
+    value.to_string()
+}
+
+fn redact_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                if REDACTED_BODY_FIELDS.contains(&key.as_str()) {
+                    *entry = serde_json::Value::String("[REDACTED]".to_string());
+                } else {
+                    redact_value(entry);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items.iter_mut() {
+                redact_value(item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Standard header summary for the Eventbrite bearer-token endpoints, with
+/// the token itself redacted.
+pub const BEARER_HEADERS: &str = "Authorization: Bearer [REDACTED]";
@@ -1,8 +1,16 @@
 mod app;
 mod ui;
 
+mod cache;
+mod checkin;
+mod command;
 mod eventbrite_attendees;
 mod eventbrite_auth;
+mod export;
+mod fuzzy;
+mod inspector;
+mod response_cache;
+mod theme;
 
 use app::App;
 use crossterm::{
@@ -66,9 +74,18 @@ fn run_app<B: ratatui::backend::Backend>(
     debug!("Entering application loop");
     let mut last_event_time = Instant::now();
     let debounce_duration = Duration::from_millis(150);
+    let mut last_reconcile_time = Instant::now();
+    let reconcile_interval = Duration::from_secs(30);
 
     loop {
         terminal.draw(|f| draw_ui(f, app))?;
+        app.poll_attendees_load();
+        app.poll_reconcile_checkins();
+
+        if last_reconcile_time.elapsed() >= reconcile_interval {
+            app.reconcile_checkins();
+            last_reconcile_time = Instant::now();
+        }
 
         if event::poll(Duration::from_millis(100))? {
             if last_event_time.elapsed() >= debounce_duration {
@@ -0,0 +1,41 @@
+//! Bounded LRU cache of ETag/Last-Modified-validated API responses, so a
+//! repeated launch or refresh can replay `If-None-Match`/`If-Modified-Since`
+//! instead of re-downloading and re-parsing pages that haven't changed.
+//! Entries are keyed by the caller (the request URL for singleton
+//! endpoints, `(event_id, page)` for attendee pages) and evicted
+//! least-recently-used once the configured capacity is exceeded.
+
+use once_cell::sync::Lazy;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+const CAPACITY_ENV_VAR: &str = "LES_PTITS_GILETS_RESPONSE_CACHE_SIZE";
+const DEFAULT_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+fn capacity() -> NonZeroUsize {
+    std::env::var(CAPACITY_ENV_VAR)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .and_then(NonZeroUsize::new)
+        .unwrap_or_else(|| NonZeroUsize::new(DEFAULT_CAPACITY).unwrap())
+}
+
+static CACHE: Lazy<Mutex<lru::LruCache<String, CachedResponse>>> =
+    Lazy::new(|| Mutex::new(lru::LruCache::new(capacity())));
+
+/// Returns the validators/body stored for `key`, if any, for use as
+/// conditional-request headers and as the fallback body on a `304`.
+pub fn get(key: &str) -> Option<CachedResponse> {
+    CACHE.lock().unwrap().get(key).cloned()
+}
+
+pub fn put(key: &str, response: CachedResponse) {
+    CACHE.lock().unwrap().put(key.to_string(), response);
+}
@@ -0,0 +1,153 @@
+//! Named style slots for the TUI, resolved from a [`ThemeName`] preset
+//! picked at runtime (see `AppView::ThemeMenu`) instead of the render layer
+//! hard-coding `Color` values, so the app stays usable on both light and
+//! dark terminals.
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+/// A color by name, serialized independently of ratatui's own (optional)
+/// serde support so themes round-trip through the JSON config reliably.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NamedColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+    DarkGray,
+}
+
+impl NamedColor {
+    fn to_color(self) -> Color {
+        match self {
+            NamedColor::Black => Color::Black,
+            NamedColor::Red => Color::Red,
+            NamedColor::Green => Color::Green,
+            NamedColor::Yellow => Color::Yellow,
+            NamedColor::Blue => Color::Blue,
+            NamedColor::Magenta => Color::Magenta,
+            NamedColor::Cyan => Color::Cyan,
+            NamedColor::White => Color::White,
+            NamedColor::Gray => Color::Gray,
+            NamedColor::DarkGray => Color::DarkGray,
+        }
+    }
+}
+
+/// A foreground/background/bold triple for one named UI slot.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct StyleDef {
+    pub fg: Option<NamedColor>,
+    pub bg: Option<NamedColor>,
+    #[serde(default)]
+    pub bold: bool,
+}
+
+impl StyleDef {
+    fn fg(color: NamedColor) -> Self {
+        Self { fg: Some(color), bg: None, bold: false }
+    }
+
+    fn fg_bold(color: NamedColor) -> Self {
+        Self { fg: Some(color), bg: None, bold: true }
+    }
+
+    pub fn to_style(self) -> Style {
+        let mut style = Style::default();
+        if let Some(fg) = self.fg {
+            style = style.fg(fg.to_color());
+        }
+        if let Some(bg) = self.bg {
+            style = style.bg(bg.to_color());
+        }
+        if self.bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        style
+    }
+}
+
+/// Named style slots consulted by the render layer instead of literal
+/// `Color` values for menus, tables, popups and status bars. The fuzzy-match
+/// highlight in the attendee table (yellow) and the check-in mark (green)
+/// keep fixed colors and do not change with the theme.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Theme {
+    pub menu_item: StyleDef,
+    pub selected_item: StyleDef,
+    pub table_header: StyleDef,
+    pub selected_cell: StyleDef,
+    pub input_focus: StyleDef,
+    pub error_text: StyleDef,
+    /// Chrome for input popups and the command palette (`draw_popup`,
+    /// `draw_command_palette`): these cover most of the screen with a
+    /// solid background, so they need their own fg/bg pair rather than
+    /// reusing a text-only slot.
+    pub popup: StyleDef,
+}
+
+/// Which built-in [`Theme`] preset is active; persisted in the config so
+/// the choice survives restarts.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    Dark,
+    Light,
+}
+
+impl Default for ThemeName {
+    fn default() -> Self {
+        ThemeName::Dark
+    }
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 2] = [ThemeName::Dark, ThemeName::Light];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeName::Dark => "Sombre",
+            ThemeName::Light => "Clair",
+        }
+    }
+
+    /// Resolves the preset into a concrete [`Theme`] the render layer can
+    /// consult. Dark mirrors the colors the UI used before theming existed;
+    /// light swaps them for ones that stay legible on a light background.
+    pub fn resolve(self) -> Theme {
+        match self {
+            ThemeName::Dark => Theme {
+                menu_item: StyleDef { fg: None, bg: None, bold: false },
+                selected_item: StyleDef::fg_bold(NamedColor::Magenta),
+                table_header: StyleDef { fg: None, bg: None, bold: true },
+                selected_cell: StyleDef {
+                    fg: Some(NamedColor::White),
+                    bg: Some(NamedColor::Magenta),
+                    bold: false,
+                },
+                input_focus: StyleDef::fg(NamedColor::Magenta),
+                error_text: StyleDef::fg(NamedColor::Red),
+                popup: StyleDef { fg: Some(NamedColor::White), bg: Some(NamedColor::Black), bold: false },
+            },
+            ThemeName::Light => Theme {
+                menu_item: StyleDef { fg: Some(NamedColor::Black), bg: None, bold: false },
+                selected_item: StyleDef::fg_bold(NamedColor::Blue),
+                table_header: StyleDef { fg: Some(NamedColor::Black), bg: None, bold: true },
+                selected_cell: StyleDef {
+                    fg: Some(NamedColor::White),
+                    bg: Some(NamedColor::Blue),
+                    bold: false,
+                },
+                input_focus: StyleDef::fg(NamedColor::Blue),
+                error_text: StyleDef::fg(NamedColor::Red),
+                popup: StyleDef { fg: Some(NamedColor::Black), bg: Some(NamedColor::White), bold: false },
+            },
+        }
+    }
+}
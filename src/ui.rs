@@ -2,6 +2,7 @@ use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table},
 };
 
@@ -12,9 +13,14 @@ pub fn draw_ui(f: &mut Frame, app: &App) {
         AppView::MainMenu => draw_main_menu(f, app),
         AppView::ListNextEventAttendeesMenu => draw_submenu(f, app),
         AppView::SettingsMenu => draw_settings_menu(f, app),
-        AppView::SetClientIdPopup => draw_popup(f, "Enter CLIENT_ID", &app.input_buffer),
-        AppView::SetClientSecretPopup => draw_popup(f, "Enter CLIENT_SECRET", &app.input_buffer),
+        AppView::SetClientIdPopup => draw_popup(f, app, "Enter CLIENT_ID", &app.input_buffer),
+        AppView::SetClientSecretPopup => draw_popup(f, app, "Enter CLIENT_SECRET", &app.input_buffer),
         AppView::FindByNameMenu => draw_find_by_name_menu(f, app),
+        AppView::AccountsMenu => draw_accounts_menu(f, app),
+        AppView::InspectorMenu => draw_inspector(f, app),
+        AppView::CommandMode => draw_command_palette(f, app),
+        AppView::ExportPopup => draw_popup(f, app, "Exporter les participants (.csv ou .json)", &app.input_buffer),
+        AppView::ThemeMenu => draw_theme_menu(f, app),
     }
 }
 
@@ -23,6 +29,7 @@ fn draw_main_menu(f: &mut Frame, app: &App) {
         ListItem::new("Liste des participants à la prochaine maraude"),
         ListItem::new("Paramétrage"),
         ListItem::new("Rechercher un participant par nom"),
+        ListItem::new("Inspecteur de requêtes API"),
     ];
 
     let mut state = ListState::default();
@@ -30,11 +37,7 @@ fn draw_main_menu(f: &mut Frame, app: &App) {
 
     let list = List::new(items)
         .block(Block::default().title("Menu Principal").borders(Borders::ALL))
-        .highlight_style(
-            Style::default()
-                .fg(Color::Magenta)
-                .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(app.theme.selected_item.to_style())
         .highlight_symbol(">> ");
 
     // Divide the screen into two parts: the list and the status bar
@@ -53,27 +56,93 @@ fn draw_main_menu(f: &mut Frame, app: &App) {
     f.render_stateful_widget(list, chunks[0], &mut state);
 
     // Create and render the status bar in the bottom chunk
-    let status_bar = Paragraph::new("Appuyez sur 'Échap' pour quitter")
-        .style(Style::default().fg(Color::Magenta))
-        .alignment(Alignment::Center);
+    let status_bar = match &app.status_message {
+        Some(message) => Paragraph::new(message.as_str())
+            .style(app.theme.error_text.to_style())
+            .alignment(Alignment::Center),
+        None => Paragraph::new("Appuyez sur 'Échap' pour quitter")
+            .style(app.theme.input_focus.to_style())
+            .alignment(Alignment::Center),
+    };
     f.render_widget(status_bar, chunks[1]);
 }
 
+/// Renders `text` as a ratatui `Line`, making the characters at `matches`
+/// (char indices returned by `fuzzy::matched_positions`) stand out so the
+/// incremental filter shows why a row matched.
+fn highlight_line(text: &str, matches: &[usize]) -> Line<'static> {
+    let spans: Vec<Span> = text
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if matches.contains(&i) {
+                Span::styled(
+                    c.to_string(),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::raw(c.to_string())
+            }
+        })
+        .collect();
+    Line::from(spans)
+}
+
+/// Renders a `fetched_at` unix timestamp as a short, human-readable age
+/// ("il y a 3 min") for the offline-cache indicator in `draw_submenu`.
+fn format_cache_age(fetched_at: u64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(fetched_at);
+    let age_secs = now.saturating_sub(fetched_at);
+
+    if age_secs < 60 {
+        "il y a quelques secondes".to_string()
+    } else if age_secs < 3600 {
+        format!("il y a {} min", age_secs / 60)
+    } else if age_secs < 86400 {
+        format!("il y a {} h", age_secs / 3600)
+    } else {
+        format!("il y a {} j", age_secs / 86400)
+    }
+}
+
 fn draw_submenu(f: &mut Frame, app: &App) {
     let event_date = app.event_date.as_deref().unwrap_or("<unknown date>");
-    let title = format!("Participants à la maraude du {}", event_date);
+    let mut title = format!("Participants à la maraude du {}", event_date);
+    if let Some(cached_at) = app.attendees_cached_at {
+        title.push_str(&format!(" — {} (hors ligne)", format_cache_age(cached_at)));
+    }
+    if !app.attendee_filter.is_empty() {
+        title.push_str(&format!(" — filtre: \"{}\"", app.attendee_filter));
+    }
+
+    let visible_attendees = app.filtered_attendees();
 
     if app.attendees.is_empty() {
-        let no_attendees = Paragraph::new("No attendees found.")
+        let message = match app.loading_progress {
+            Some((loaded, 0)) => format!("Chargement des participants… ({} pages)", loaded),
+            Some((loaded, total)) => format!("Chargement des participants… ({}/{} pages)", loaded, total),
+            None => "No attendees found.".to_string(),
+        };
+        let no_attendees = Paragraph::new(message)
             .block(Block::default().title(title).borders(Borders::ALL))
             .alignment(Alignment::Center);
         f.render_widget(no_attendees, f.size());
         return;
     }
 
+    if visible_attendees.is_empty() {
+        let no_match = Paragraph::new("Aucun participant ne correspond au filtre.")
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .alignment(Alignment::Center);
+        f.render_widget(no_match, f.size());
+        return;
+    }
+
     // Create table rows for attendees
-    let rows: Vec<Row> = app
-    .attendees
+    let rows: Vec<Row> = visible_attendees
     .iter()
     .enumerate()
     .map(|(row_idx, attendee)| {
@@ -101,65 +170,78 @@ fn draw_submenu(f: &mut Frame, app: &App) {
 
         let email = attendee.profile.email.clone().unwrap_or_default().to_lowercase();
 
+        let first_name_line = highlight_line(&first_name, &crate::fuzzy::matched_positions(&app.attendee_filter, &first_name));
+        let last_name_line = highlight_line(&last_name, &crate::fuzzy::matched_positions(&app.attendee_filter, &last_name));
+
         let cells: Vec<Cell> = vec![
-            Cell::from(first_name).style(if row_idx == app.selected_row && app.selected_col == 0 {
-                Style::default().bg(Color::Magenta).fg(Color::White)
+            Cell::from(if app.selected.contains(&row_idx) { "[x]" } else { "[ ]" }),
+            Cell::from(first_name_line).style(if row_idx == app.selected_row && app.selected_col == 0 {
+                app.theme.selected_cell.to_style()
             } else {
                 Style::default()
             }),
-            Cell::from(last_name).style(if row_idx == app.selected_row && app.selected_col == 1 {
-                Style::default().bg(Color::Magenta).fg(Color::White)
+            Cell::from(last_name_line).style(if row_idx == app.selected_row && app.selected_col == 1 {
+                app.theme.selected_cell.to_style()
             } else {
                 Style::default()
             }),
             Cell::from(email).style(if row_idx == app.selected_row && app.selected_col == 2 {
-                Style::default().bg(Color::Magenta).fg(Color::White)
+                app.theme.selected_cell.to_style()
             } else {
                 Style::default()
             }),
             Cell::from(attendee.profile.cell_phone.clone().unwrap_or_default())
                 .style(if row_idx == app.selected_row && app.selected_col == 3 {
-                    Style::default().bg(Color::Magenta).fg(Color::White)
+                    app.theme.selected_cell.to_style()
                 } else {
                     Style::default()
                 }),
             Cell::from(attendee.birthdate.clone().unwrap_or_default())
                 .style(if row_idx == app.selected_row && app.selected_col == 4 {
-                    Style::default().bg(Color::Magenta).fg(Color::White)
+                    app.theme.selected_cell.to_style()
                 } else {
                     Style::default()
                 }),
             Cell::from(attendee.ticket_class_name.clone().unwrap_or_default())
                 .style(if row_idx == app.selected_row && app.selected_col == 5 {
-                    Style::default().bg(Color::Magenta).fg(Color::White)
+                    app.theme.selected_cell.to_style()
                 } else {
                     Style::default()
                 }),
             Cell::from(attendee.created.clone())
                 .style(if row_idx == app.selected_row && app.selected_col == 6 {
-                    Style::default().bg(Color::Magenta).fg(Color::White)
+                    app.theme.selected_cell.to_style()
                 } else {
                     Style::default()
                 }),
+            Cell::from(if attendee.checked_in { "✓" } else { "" })
+                .style(if row_idx == app.selected_row && app.selected_col == 7 {
+                    app.theme.selected_cell.to_style()
+                } else {
+                    Style::default().fg(Color::Green)
+                }),
         ];
 
         Row::new(cells)
     })
     .collect();
     let widths = [
-        Constraint::Percentage(15),
-        Constraint::Percentage(15),
-        Constraint::Percentage(20),
-        Constraint::Percentage(15),
-        Constraint::Percentage(10),
-        Constraint::Percentage(10),
-        Constraint::Percentage(10),
+        Constraint::Length(3),
+        Constraint::Percentage(14),
+        Constraint::Percentage(14),
+        Constraint::Percentage(17),
+        Constraint::Percentage(13),
+        Constraint::Percentage(9),
+        Constraint::Percentage(9),
+        Constraint::Percentage(9),
+        Constraint::Percentage(9),
     ];
 
     // Create the table
     let table = Table::new(rows, widths)
         .header(
             Row::new(vec![
+                Cell::from(""),
                 Cell::from("Prénom"),
                 Cell::from("Nom"),
                 Cell::from("Email"),
@@ -167,19 +249,13 @@ fn draw_submenu(f: &mut Frame, app: &App) {
                 Cell::from("Date de naissance"),
                 Cell::from("Type d'inscription"),
                 Cell::from("Date d'inscription"),
+                Cell::from("Présent"),
             ])
-            .style(
-                Style::default()
-                    .add_modifier(Modifier::BOLD),
-            ),
+            .style(app.theme.table_header.to_style()),
         )
         .block(Block::default().title(title).borders(Borders::ALL))
         .widths(widths)
-        .highlight_style(
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        );
+        .highlight_style(app.theme.selected_item.to_style());
 
     // Layout for the table and status bar
     let chunks = Layout::default()
@@ -197,30 +273,47 @@ fn draw_submenu(f: &mut Frame, app: &App) {
     f.render_widget(table, chunks[0]);
 
     // Create and render the status bar in the bottom chunk
-    let status_bar = Paragraph::new("Appuyez sur 'Échap' pour revenir au menu principal")
-        .style(Style::default().fg(Color::Magenta))
+    let status_text = if app.filtering {
+        "Filtrer: tapez un nom, Entrée/Échap pour valider"
+    } else {
+        "'/' filtrer, 'espace' sélectionner, 'a'/'A' tout/aucun, 'y' copier, 'e' exporter, 'Entrée' check-in, 'c' copier la cellule, 'r' rafraîchir, Échap retour"
+    };
+    let status_bar = Paragraph::new(status_text)
+        .style(app.theme.input_focus.to_style())
         .alignment(Alignment::Center);
     f.render_widget(status_bar, chunks[1]);
 }
 
 fn draw_settings_menu(f: &mut Frame, app: &App) {
-    // Charger la configuration actuelle
-    let config = crate::eventbrite_auth::load_config().unwrap_or_default();
+    // La configuration a déjà été chargée (et, le cas échéant, déchiffrée)
+    // une seule fois dans App::new(); ne jamais recharger ici, sous peine de
+    // redemander la passphrase à chaque rendu.
+    let config = &app.config;
+    let active_account = config.accounts.active_account();
 
     // Préparer les éléments du menu
-    let client_id_display = match &config.client_id {
+    let client_id_display = match active_account.and_then(|a| a.client_id.as_ref()) {
         Some(client_id) => format!("Client ID EventBrite: {}", client_id),
         None => "Client ID EventBrite: <non défini>".to_string(),
     };
 
-    let client_secret_display = match &config.client_secret {
+    let client_secret_display = match active_account.and_then(|a| a.client_secret.as_ref()) {
         Some(_) => "Client Secret EventBrite: *****".to_string(),
         None => "Client Secret EventBrite: <non défini>".to_string(),
     };
 
+    let accounts_display = format!(
+        "Comptes Eventbrite ({})",
+        active_account.map(|a| a.name.as_str()).unwrap_or("aucun")
+    );
+
+    let theme_display = format!("Thème ({})", config.theme_name.label());
+
     let items = vec![
         ListItem::new(client_id_display),
         ListItem::new(client_secret_display),
+        ListItem::new(accounts_display),
+        ListItem::new(theme_display),
     ];
 
     // Mettre en surbrillance l'élément sélectionné
@@ -230,11 +323,7 @@ fn draw_settings_menu(f: &mut Frame, app: &App) {
     // Créer le widget de liste
     let list = List::new(items)
         .block(Block::default().title("Paramétrage").borders(Borders::ALL))
-        .highlight_style(
-            Style::default()
-                .fg(Color::Magenta)
-                .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(app.theme.selected_item.to_style())
         .highlight_symbol(">> ");
 
     // Diviser l'écran en deux parties : liste et barre de statut
@@ -254,19 +343,46 @@ fn draw_settings_menu(f: &mut Frame, app: &App) {
 
     // Créer et rendre la barre de statut dans la partie inférieure
     let status_bar = Paragraph::new("Appuyez sur 'Échap' pour revenir au menu principal")
-        .style(Style::default().fg(Color::Magenta))
+        .style(app.theme.input_focus.to_style())
+        .alignment(Alignment::Center);
+    f.render_widget(status_bar, chunks[1]);
+}
+
+fn draw_theme_menu(f: &mut Frame, app: &App) {
+    let items: Vec<ListItem> = crate::theme::ThemeName::ALL
+        .iter()
+        .map(|theme_name| ListItem::new(theme_name.label()))
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.theme_menu_index));
+
+    let list = List::new(items)
+        .block(Block::default().title("Thème").borders(Borders::ALL))
+        .highlight_style(app.theme.selected_item.to_style())
+        .highlight_symbol(">> ");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(f.size());
+
+    f.render_stateful_widget(list, chunks[0], &mut state);
+
+    let status_bar = Paragraph::new("Entrée pour activer, Échap pour revenir")
+        .style(app.theme.input_focus.to_style())
         .alignment(Alignment::Center);
     f.render_widget(status_bar, chunks[1]);
 }
 
-fn draw_popup(f: &mut Frame, title: &str, input: &str) {
+fn draw_popup(f: &mut Frame, app: &App, title: &str, input: &str) {
     let size = f.size();
     let popup_area = centered_rect(60, 20, size);
 
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black).fg(Color::White));
+        .style(app.theme.popup.to_style());
 
     let paragraph = Paragraph::new(format!(
         "{}\n\nPress Enter to confirm or Esc to cancel",
@@ -278,6 +394,100 @@ fn draw_popup(f: &mut Frame, title: &str, input: &str) {
     f.render_widget(paragraph, popup_area);
 }
 
+fn draw_command_palette(f: &mut Frame, app: &App) {
+    let size = f.size();
+    let popup_area = centered_rect(60, 20, size);
+
+    let block = Block::default()
+        .title("Palette de commandes")
+        .borders(Borders::ALL)
+        .style(app.theme.popup.to_style());
+
+    let paragraph = Paragraph::new(format!(
+        ":{}\n\nfind \"Prénom\" \"Nom\" · copy <champ> · goto <vue> · set client_id|client_secret <valeur> · export csv · quit\nEntrée pour exécuter, Échap pour annuler",
+        app.command_input
+    ))
+    .block(block)
+    .alignment(Alignment::Left);
+
+    f.render_widget(paragraph, popup_area);
+}
+
+fn draw_inspector(f: &mut Frame, app: &App) {
+    let entries = crate::inspector::INSPECTOR.entries();
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+        .split(f.size());
+
+    if entries.is_empty() {
+        let empty = Paragraph::new("Aucune requête Eventbrite interceptée pour le moment.")
+            .block(Block::default().title("Inspecteur API").borders(Borders::ALL))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, f.size());
+        return;
+    }
+
+    let rows: Vec<Row> = entries
+        .iter()
+        .map(|entry| {
+            Row::new(vec![
+                Cell::from(entry.timestamp.clone()),
+                Cell::from(entry.method.clone()),
+                Cell::from(entry.url.clone()),
+                Cell::from(
+                    entry
+                        .status
+                        .map(|s| s.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(format!("{}ms", entry.latency.as_millis())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(6),
+        Constraint::Percentage(55),
+        Constraint::Length(6),
+        Constraint::Length(8),
+    ];
+
+    let mut table_state = ratatui::widgets::TableState::default();
+    table_state.select(Some(app.inspector_menu_index));
+
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(vec![
+                Cell::from("Heure"),
+                Cell::from("Méthode"),
+                Cell::from("URL"),
+                Cell::from("Statut"),
+                Cell::from("Latence"),
+            ])
+            .style(app.theme.table_header.to_style()),
+        )
+        .block(Block::default().title("Requêtes récentes").borders(Borders::ALL))
+        .widths(widths)
+        .highlight_style(app.theme.selected_item.to_style());
+
+    f.render_stateful_widget(table, chunks[0], &mut table_state);
+
+    let selected = &entries[app.inspector_menu_index.min(entries.len() - 1)];
+    let detail = format!(
+        "{}\n\n{}\n\n{}",
+        selected.headers,
+        format!("{} {} -> {:?}", selected.method, selected.url, selected.status),
+        selected.response_body
+    );
+    let detail_paragraph = Paragraph::new(detail)
+        .block(Block::default().title("Détail").borders(Borders::ALL))
+        .alignment(Alignment::Left);
+    f.render_widget(detail_paragraph, chunks[1]);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, rect: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -304,6 +514,46 @@ fn centered_rect(percent_x: u16, percent_y: u16, rect: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+fn draw_accounts_menu(f: &mut Frame, app: &App) {
+    // Voir le commentaire de `draw_settings_menu` : on réutilise la
+    // configuration déjà chargée par `App` plutôt que d'appeler `load_config`.
+    let config = &app.config;
+
+    let mut items: Vec<ListItem> = config
+        .accounts
+        .accounts
+        .iter()
+        .enumerate()
+        .map(|(i, account)| {
+            let marker = if i == config.accounts.active { "* " } else { "  " };
+            ListItem::new(format!("{}{}", marker, account.name))
+        })
+        .collect();
+    items.push(ListItem::new("+ Ajouter un compte"));
+
+    let mut state = ListState::default();
+    state.select(Some(app.accounts_menu_index));
+
+    let list = List::new(items)
+        .block(Block::default().title("Comptes Eventbrite").borders(Borders::ALL))
+        .highlight_style(app.theme.selected_item.to_style())
+        .highlight_symbol(">> ");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(f.size());
+
+    f.render_stateful_widget(list, chunks[0], &mut state);
+
+    let status_bar = Paragraph::new(
+        "Entrée pour activer/ajouter, 'd' pour supprimer, Échap pour revenir",
+    )
+    .style(app.theme.input_focus.to_style())
+    .alignment(Alignment::Center);
+    f.render_widget(status_bar, chunks[1]);
+}
+
 fn draw_find_by_name_menu(f: &mut Frame, app: &App) {
     let state = &app.name_search_state;
     let chunks = Layout::default()
@@ -322,10 +572,10 @@ fn draw_find_by_name_menu(f: &mut Frame, app: &App) {
 
     let first_name_paragraph = Paragraph::new(first_name)
         .block(Block::default().borders(Borders::ALL).title("Prénom"))
-        .style(if state.focus == 0 { Style::default().fg(Color::Magenta) } else { Style::default() });
+        .style(if state.focus == 0 { app.theme.input_focus.to_style() } else { Style::default() });
     let last_name_paragraph = Paragraph::new(last_name)
         .block(Block::default().borders(Borders::ALL).title("Nom"))
-        .style(if state.focus == 1 { Style::default().fg(Color::Magenta) } else { Style::default() });
+        .style(if state.focus == 1 { app.theme.input_focus.to_style() } else { Style::default() });
 
     f.render_widget(first_name_paragraph, chunks[0]);
     f.render_widget(last_name_paragraph, chunks[1]);
@@ -350,7 +600,7 @@ fn draw_find_by_name_menu(f: &mut Frame, app: &App) {
 
     let results_list = List::new(results)
         .block(Block::default().borders(Borders::ALL).title("Événements trouvés"))
-        .highlight_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD));
+        .highlight_style(app.theme.selected_item.to_style());
 
     f.render_stateful_widget(results_list, chunks[2], &mut list_state);
 
@@ -361,7 +611,7 @@ fn draw_find_by_name_menu(f: &mut Frame, app: &App) {
         "↑/↓ pour défiler, Échap pour revenir"
     };
     let status_bar = Paragraph::new(status)
-        .style(Style::default().fg(Color::Magenta))
+        .style(app.theme.input_focus.to_style())
         .alignment(Alignment::Center);
     f.render_widget(status_bar, chunks[3]);
 }